@@ -16,21 +16,39 @@ pub struct Config {
     pub settings_file: PathBuf,
     /// Filter history file path
     pub filter_history_file: PathBuf,
+    /// URL history file path
+    pub url_history_file: PathBuf,
+    /// Header presets file path
+    pub header_presets_file: PathBuf,
+    /// Cached result of the last release check
+    pub update_check_file: PathBuf,
+    /// Default debug log file, used when `log_to_file` is on
+    pub log_file: PathBuf,
+    /// Recovery copy of the request being edited, removed on a clean exit
+    pub draft_file: PathBuf,
 }
 
+/// File in the default config directory remembering the last `--config-dir`
+const LAST_CONFIG_DIR_FILE: &str = "last_config_dir";
+
 impl Config {
-    pub fn new() -> Result<Self> {
-        // Use ~/.config on all platforms for consistency
-        let base_dir = dirs::home_dir()
-            .unwrap_or_else(|| PathBuf::from("."))
-            .join(".config")
-            .join("restui");
+    /// Build the config rooted at `config_dir`, or at the directory remembered from
+    /// the last `--config-dir`, or at the default `~/.config/restui`
+    pub fn new(config_dir: Option<PathBuf>) -> Result<Self> {
+        let base_dir = config_dir
+            .or_else(Self::last_config_dir)
+            .unwrap_or_else(Self::default_dir);
 
         let collections_dir = base_dir.join("collections");
         let history_file = base_dir.join("history.json");
         let environments_file = base_dir.join("environments.json");
         let settings_file = base_dir.join("settings.json");
         let filter_history_file = base_dir.join("filter_history.json");
+        let url_history_file = base_dir.join("url_history.json");
+        let header_presets_file = base_dir.join("header_presets.json");
+        let update_check_file = base_dir.join(".update_check");
+        let log_file = base_dir.join("restui.log");
+        let draft_file = base_dir.join("draft.json");
 
         Ok(Self {
             data_dir: base_dir,
@@ -39,9 +57,54 @@ impl Config {
             environments_file,
             settings_file,
             filter_history_file,
+            url_history_file,
+            header_presets_file,
+            update_check_file,
+            log_file,
+            draft_file,
         })
     }
 
+    /// Default config directory; ~/.config on all platforms for consistency
+    fn default_dir() -> PathBuf {
+        dirs::home_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join(".config")
+            .join("restui")
+    }
+
+    fn last_config_dir() -> Option<PathBuf> {
+        let content =
+            std::fs::read_to_string(Self::default_dir().join(LAST_CONFIG_DIR_FILE)).ok()?;
+        let path = content.trim();
+        (!path.is_empty()).then(|| PathBuf::from(path))
+    }
+
+    /// Remember this config directory for invocations without `--config-dir`
+    pub fn remember_dir(&self) -> Result<()> {
+        let default_dir = Self::default_dir();
+        std::fs::create_dir_all(&default_dir)?;
+        let data_dir = std::path::absolute(&self.data_dir)?;
+        std::fs::write(
+            default_dir.join(LAST_CONFIG_DIR_FILE),
+            data_dir.to_string_lossy().as_bytes(),
+        )?;
+        Ok(())
+    }
+
+    /// Collection files (`*.json`) in the collections directory, sorted by path
+    pub fn collection_files(&self) -> Result<Vec<PathBuf>> {
+        if !self.collections_dir.exists() {
+            return Ok(Vec::new());
+        }
+        let mut files: Vec<PathBuf> = std::fs::read_dir(&self.collections_dir)?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+            .collect();
+        files.sort();
+        Ok(files)
+    }
+
     /// Ensure all required directories exist
     pub fn ensure_dirs(&self) -> Result<()> {
         std::fs::create_dir_all(&self.data_dir)?;
@@ -52,6 +115,6 @@ impl Config {
 
 impl Default for Config {
     fn default() -> Self {
-        Self::new().expect("Failed to create default config")
+        Self::new(None).expect("Failed to create default config")
     }
 }
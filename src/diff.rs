@@ -0,0 +1,147 @@
+/// One line of a line-by-line diff from `a` to `b`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiffLine {
+    Same(String),
+    Removed(String),
+    Added(String),
+}
+
+/// Past this many cells in the LCS table, the changed middle is shown as a
+/// block removed and a block added instead of being matched line by line
+const MAX_LCS_CELLS: usize = 4_000_000;
+
+/// Line diff of `a` against `b`, longest common subsequence based. Unchanged
+/// lines at either end are matched first, so only the middle is compared.
+pub fn diff_lines(a: &str, b: &str) -> Vec<DiffLine> {
+    let a: Vec<&str> = a.lines().collect();
+    let b: Vec<&str> = b.lines().collect();
+
+    let prefix = a.iter().zip(&b).take_while(|(x, y)| x == y).count();
+    let suffix = a[prefix..]
+        .iter()
+        .rev()
+        .zip(b[prefix..].iter().rev())
+        .take_while(|(x, y)| x == y)
+        .count();
+    let a_mid = &a[prefix..a.len() - suffix];
+    let b_mid = &b[prefix..b.len() - suffix];
+
+    let mut result: Vec<DiffLine> = a[..prefix]
+        .iter()
+        .map(|line| DiffLine::Same(line.to_string()))
+        .collect();
+    if a_mid.len().saturating_mul(b_mid.len()) > MAX_LCS_CELLS {
+        result.extend(a_mid.iter().map(|line| DiffLine::Removed(line.to_string())));
+        result.extend(b_mid.iter().map(|line| DiffLine::Added(line.to_string())));
+    } else {
+        result.extend(lcs_diff(a_mid, b_mid));
+    }
+    result.extend(
+        a[a.len() - suffix..]
+            .iter()
+            .map(|line| DiffLine::Same(line.to_string())),
+    );
+    result
+}
+
+fn lcs_diff(a: &[&str], b: &[&str]) -> Vec<DiffLine> {
+    // lengths[i][j] = LCS length of a[i..] and b[j..]
+    let width = b.len() + 1;
+    let mut lengths = vec![0u32; (a.len() + 1) * width];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lengths[i * width + j] = if a[i] == b[j] {
+                lengths[(i + 1) * width + j + 1] + 1
+            } else {
+                lengths[(i + 1) * width + j].max(lengths[i * width + j + 1])
+            };
+        }
+    }
+
+    let mut result = Vec::with_capacity(a.len() + b.len());
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() && j < b.len() {
+        if a[i] == b[j] {
+            result.push(DiffLine::Same(a[i].to_string()));
+            i += 1;
+            j += 1;
+        } else if lengths[(i + 1) * width + j] >= lengths[i * width + j + 1] {
+            result.push(DiffLine::Removed(a[i].to_string()));
+            i += 1;
+        } else {
+            result.push(DiffLine::Added(b[j].to_string()));
+            j += 1;
+        }
+    }
+    result.extend(
+        a[i..]
+            .iter()
+            .map(|line| DiffLine::Removed(line.to_string())),
+    );
+    result.extend(b[j..].iter().map(|line| DiffLine::Added(line.to_string())));
+    result
+}
+
+/// Pair diff lines into side-by-side rows: unchanged lines on both sides, and
+/// each run of removals lined up against the additions that follow it
+pub fn side_by_side(diff: &[DiffLine]) -> Vec<(Option<&DiffLine>, Option<&DiffLine>)> {
+    let mut rows = Vec::new();
+    let mut idx = 0;
+    while idx < diff.len() {
+        if let DiffLine::Same(_) = diff[idx] {
+            rows.push((Some(&diff[idx]), Some(&diff[idx])));
+            idx += 1;
+            continue;
+        }
+        let removed_start = idx;
+        while idx < diff.len() && matches!(diff[idx], DiffLine::Removed(_)) {
+            idx += 1;
+        }
+        let added_start = idx;
+        while idx < diff.len() && matches!(diff[idx], DiffLine::Added(_)) {
+            idx += 1;
+        }
+        let removed = &diff[removed_start..added_start];
+        let added = &diff[added_start..idx];
+        for row in 0..removed.len().max(added.len()) {
+            rows.push((removed.get(row), added.get(row)));
+        }
+    }
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff_lines() {
+        let a = "{\n  \"id\": 1,\n  \"name\": \"Ada\"\n}";
+        let b = "{\n  \"id\": 2,\n  \"name\": \"Ada\",\n  \"admin\": true\n}";
+        assert_eq!(
+            diff_lines(a, b),
+            vec![
+                DiffLine::Same("{".to_string()),
+                DiffLine::Removed("  \"id\": 1,".to_string()),
+                DiffLine::Removed("  \"name\": \"Ada\"".to_string()),
+                DiffLine::Added("  \"id\": 2,".to_string()),
+                DiffLine::Added("  \"name\": \"Ada\",".to_string()),
+                DiffLine::Added("  \"admin\": true".to_string()),
+                DiffLine::Same("}".to_string()),
+            ]
+        );
+        assert!(diff_lines("a\nb", "a\nb")
+            .iter()
+            .all(|line| matches!(line, DiffLine::Same(_))));
+    }
+
+    #[test]
+    fn test_side_by_side() {
+        let diff = diff_lines("a\nb\nc", "a\nx\ny\nc");
+        let rows = side_by_side(&diff);
+        assert_eq!(rows.len(), 4);
+        assert_eq!(rows[1].0, Some(&DiffLine::Removed("b".to_string())));
+        assert_eq!(rows[1].1, Some(&DiffLine::Added("x".to_string())));
+        assert_eq!(rows[2], (None, Some(&DiffLine::Added("y".to_string()))));
+    }
+}
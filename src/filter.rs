@@ -60,10 +60,69 @@ pub fn apply_jq_filter(json: &str, query: &str) -> Result<String, String> {
     }
 }
 
+/// Split a jq expression into its top-level `|` stages, trimmed. Pipes inside
+/// strings or brackets, and the `|=` update operator, do not split.
+pub fn split_pipeline(query: &str) -> Vec<String> {
+    let mut stages = Vec::new();
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut escaped = false;
+    let mut start = 0;
+    let mut chars = query.char_indices().peekable();
+    while let Some((idx, c)) = chars.next() {
+        if in_string {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match c {
+            '"' => in_string = true,
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' => depth = depth.saturating_sub(1),
+            '|' if depth == 0 && chars.peek().map(|&(_, next)| next) != Some('=') => {
+                stages.push(query[start..idx].trim().to_string());
+                start = idx + 1;
+            }
+            _ => {}
+        }
+    }
+    stages.push(query[start..].trim().to_string());
+    stages
+}
+
+/// Join pipeline stages back into one expression, skipping empty ones
+pub fn join_pipeline(stages: &[String]) -> String {
+    stages
+        .iter()
+        .map(|stage| stage.trim())
+        .filter(|stage| !stage.is_empty())
+        .collect::<Vec<_>>()
+        .join(" | ")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_split_pipeline() {
+        assert_eq!(split_pipeline(". | .data|.[0]"), vec![".", ".data", ".[0]"]);
+        assert_eq!(split_pipeline(".a |"), vec![".a", ""]);
+        assert_eq!(
+            split_pipeline(r#"map(.a | .b) | select(.s == "x|y\"|")"#),
+            vec!["map(.a | .b)", r#"select(.s == "x|y\"|")"#]
+        );
+        assert_eq!(split_pipeline(".a |= 1"), vec![".a |= 1"]);
+        assert_eq!(
+            join_pipeline(&split_pipeline(". | .data |  | .[0]")),
+            ". | .data | .[0]"
+        );
+    }
+
     #[test]
     fn test_identity_filter() {
         let json = r#"{"name": "test"}"#;
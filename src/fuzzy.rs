@@ -0,0 +1,60 @@
+/// Score `candidate` against `query`, or None when the query's characters do
+/// not all appear in it in order (case-insensitive). Higher is better: runs of
+/// consecutive characters and matches at the start of a word score extra,
+/// characters skipped between matches cost a little. An empty query matches
+/// everything with a score of 0.
+pub fn fuzzy_score(query: &str, candidate: &str) -> Option<i64> {
+    let candidate: Vec<char> = candidate.chars().collect();
+    let mut score = 0;
+    let mut next = 0;
+    let mut previous_match: Option<usize> = None;
+
+    for wanted in query.chars().flat_map(char::to_lowercase) {
+        let offset = candidate[next..]
+            .iter()
+            .position(|c| c.to_lowercase().eq(std::iter::once(wanted)))?;
+        let index = next + offset;
+
+        score += 1;
+        match previous_match {
+            Some(previous) if previous + 1 == index => score += 5,
+            _ => score -= offset as i64,
+        }
+        let word_start = index == 0
+            || !candidate[index - 1].is_alphanumeric()
+            || (candidate[index - 1].is_lowercase() && candidate[index].is_uppercase());
+        if word_start {
+            score += 3;
+        }
+
+        previous_match = Some(index);
+        next = index + 1;
+    }
+
+    Some(score)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fuzzy_score() {
+        assert_eq!(fuzzy_score("", "anything"), Some(0));
+        assert!(fuzzy_score("tkn", "api_token").is_some());
+        assert!(fuzzy_score("TOKEN", "api_token").is_some());
+        assert_eq!(fuzzy_score("nkt", "api_token"), None);
+        assert_eq!(fuzzy_score("tokens", "api_token"), None);
+
+        // Consecutive and word-start matches rank first
+        let exact = fuzzy_score("token", "api_token").unwrap();
+        let scattered = fuzzy_score("token", "to_a_kite_in_the_new").unwrap();
+        assert!(exact > scattered);
+        let prefix = fuzzy_score("base", "base_url").unwrap();
+        let inner = fuzzy_score("base", "database_url").unwrap();
+        assert!(prefix > inner);
+        let camel = fuzzy_score("au", "apiUrl").unwrap();
+        let plain = fuzzy_score("au", "apiurl").unwrap();
+        assert!(camel > plain);
+    }
+}
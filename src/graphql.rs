@@ -0,0 +1,264 @@
+use anyhow::{anyhow, Result};
+use serde_json::Value;
+
+/// Standard introspection query (types, fields and arguments only)
+pub const INTROSPECTION_QUERY: &str = r#"query IntrospectionQuery {
+  __schema {
+    queryType { name }
+    mutationType { name }
+    subscriptionType { name }
+    types {
+      kind
+      name
+      description
+      fields(includeDeprecated: true) {
+        name
+        description
+        args { name description type { ...TypeRef } }
+        type { ...TypeRef }
+      }
+      inputFields { name description type { ...TypeRef } }
+      enumValues(includeDeprecated: true) { name description }
+    }
+  }
+}
+
+fragment TypeRef on __Type {
+  kind
+  name
+  ofType {
+    kind
+    name
+    ofType {
+      kind
+      name
+      ofType {
+        kind
+        name
+        ofType { kind name }
+      }
+    }
+  }
+}"#;
+
+/// An argument of a GraphQL field
+#[derive(Debug, Clone)]
+pub struct GraphQLArg {
+    pub name: String,
+    pub type_name: String,
+}
+
+/// A field (or input field / enum value) of a GraphQL type
+#[derive(Debug, Clone)]
+pub struct GraphQLField {
+    pub name: String,
+    pub type_name: String,
+    pub description: Option<String>,
+    pub args: Vec<GraphQLArg>,
+}
+
+impl GraphQLField {
+    /// Text inserted into the body when the field is picked, with argument stubs
+    pub fn insertion_text(&self) -> String {
+        if self.args.is_empty() {
+            return self.name.clone();
+        }
+        let args: Vec<String> = self
+            .args
+            .iter()
+            .map(|arg| format!("{}: ", arg.name))
+            .collect();
+        format!("{}({})", self.name, args.join(", "))
+    }
+}
+
+/// A named type from the schema
+#[derive(Debug, Clone)]
+pub struct GraphQLType {
+    pub name: String,
+    pub kind: String,
+    pub description: Option<String>,
+    pub fields: Vec<GraphQLField>,
+}
+
+/// Browsable schema built from an introspection response
+#[derive(Debug, Clone)]
+pub struct GraphQLSchema {
+    /// URL the schema was fetched from (used to invalidate the cache)
+    pub url: String,
+    pub query_type: Option<String>,
+    pub mutation_type: Option<String>,
+    pub types: Vec<GraphQLType>,
+}
+
+impl GraphQLSchema {
+    /// Parse the JSON body of an introspection response
+    pub fn from_response(url: &str, body: &str) -> Result<Self> {
+        let json: Value = serde_json::from_str(body)?;
+
+        if let Some(message) = json
+            .get("errors")
+            .and_then(|errors| errors.get(0))
+            .and_then(|error| error.get("message"))
+            .and_then(Value::as_str)
+        {
+            return Err(anyhow!(message.to_string()));
+        }
+
+        let schema = json
+            .pointer("/data/__schema")
+            .ok_or_else(|| anyhow!("Response has no data.__schema"))?;
+
+        let root_name = |key: &str| {
+            schema
+                .pointer(&format!("/{}/name", key))
+                .and_then(Value::as_str)
+                .map(String::from)
+        };
+
+        let mut types: Vec<GraphQLType> = schema
+            .get("types")
+            .and_then(Value::as_array)
+            .map(|types| types.iter().filter_map(parse_type).collect())
+            .unwrap_or_default();
+        // Hide the introspection types themselves
+        types.retain(|ty| !ty.name.starts_with("__"));
+        types.sort_by_key(|ty| ty.name.to_lowercase());
+
+        Ok(Self {
+            url: url.to_string(),
+            query_type: root_name("queryType"),
+            mutation_type: root_name("mutationType"),
+            types,
+        })
+    }
+}
+
+fn parse_type(value: &Value) -> Option<GraphQLType> {
+    let name = value.get("name")?.as_str()?.to_string();
+    let kind = value.get("kind")?.as_str().unwrap_or_default().to_string();
+
+    // Objects/interfaces have fields, input objects have inputFields, enums have values
+    let fields = ["fields", "inputFields", "enumValues"]
+        .iter()
+        .filter_map(|key| value.get(*key).and_then(Value::as_array))
+        .flatten()
+        .filter_map(parse_field)
+        .collect();
+
+    Some(GraphQLType {
+        name,
+        kind,
+        description: description(value),
+        fields,
+    })
+}
+
+fn parse_field(value: &Value) -> Option<GraphQLField> {
+    let name = value.get("name")?.as_str()?.to_string();
+    let args = value
+        .get("args")
+        .and_then(Value::as_array)
+        .map(|args| {
+            args.iter()
+                .filter_map(|arg| {
+                    Some(GraphQLArg {
+                        name: arg.get("name")?.as_str()?.to_string(),
+                        type_name: arg.get("type").map(format_type_ref).unwrap_or_default(),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Some(GraphQLField {
+        name,
+        type_name: value.get("type").map(format_type_ref).unwrap_or_default(),
+        description: description(value),
+        args,
+    })
+}
+
+fn description(value: &Value) -> Option<String> {
+    value
+        .get("description")
+        .and_then(Value::as_str)
+        .filter(|desc| !desc.is_empty())
+        .map(String::from)
+}
+
+/// Render a type reference in SDL notation, e.g. `[User!]!`
+fn format_type_ref(value: &Value) -> String {
+    let inner = || value.get("ofType").map(format_type_ref).unwrap_or_default();
+    match value.get("kind").and_then(Value::as_str) {
+        Some("NON_NULL") => format!("{}!", inner()),
+        Some("LIST") => format!("[{}]", inner()),
+        _ => value
+            .get("name")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RESPONSE: &str = r#"{
+        "data": {
+            "__schema": {
+                "queryType": { "name": "Query" },
+                "mutationType": null,
+                "types": [
+                    {
+                        "kind": "OBJECT",
+                        "name": "Query",
+                        "fields": [
+                            {
+                                "name": "user",
+                                "args": [
+                                    {
+                                        "name": "id",
+                                        "type": { "kind": "NON_NULL", "name": null, "ofType": { "kind": "SCALAR", "name": "ID" } }
+                                    }
+                                ],
+                                "type": { "kind": "OBJECT", "name": "User" }
+                            },
+                            {
+                                "name": "users",
+                                "args": [],
+                                "type": { "kind": "LIST", "name": null, "ofType": { "kind": "OBJECT", "name": "User" } }
+                            }
+                        ]
+                    },
+                    { "kind": "OBJECT", "name": "__Type", "fields": [] },
+                    { "kind": "ENUM", "name": "Role", "enumValues": [ { "name": "ADMIN" } ] }
+                ]
+            }
+        }
+    }"#;
+
+    #[test]
+    fn test_parse_introspection_response() {
+        let schema = GraphQLSchema::from_response("http://localhost/graphql", RESPONSE).unwrap();
+        assert_eq!(schema.query_type.as_deref(), Some("Query"));
+        assert!(schema.mutation_type.is_none());
+
+        let names: Vec<&str> = schema.types.iter().map(|ty| ty.name.as_str()).collect();
+        assert_eq!(names, vec!["Query", "Role"]);
+
+        let query = &schema.types[0];
+        assert_eq!(query.fields[0].args[0].type_name, "ID!");
+        assert_eq!(query.fields[1].type_name, "[User]");
+        assert_eq!(query.fields[0].insertion_text(), "user(id: )");
+        assert_eq!(query.fields[1].insertion_text(), "users");
+    }
+
+    #[test]
+    fn test_parse_introspection_errors() {
+        let body = r#"{"errors":[{"message":"introspection disabled"}]}"#;
+        let err = GraphQLSchema::from_response("http://x", body).unwrap_err();
+        assert_eq!(err.to_string(), "introspection disabled");
+    }
+}
@@ -1,9 +1,23 @@
-use crate::storage::{ApiRequest, AuthConfig, AuthType, HttpMethod};
+use super::timing::{self, TimingBreakdown, TimingLayer, TimingResolver};
+use crate::storage::{ApiRequest, AuthConfig, AuthType, CompressionType, HttpMethod, Settings};
 use anyhow::Result;
 use base64::{engine::general_purpose::STANDARD, Engine};
-use reqwest::{Client, Method};
+use flate2::write::{GzEncoder, ZlibEncoder};
+use flate2::Compression;
+use lru::LruCache;
+use percent_encoding::percent_decode_str;
+use reqwest::{redirect::Policy, Client, Method, Proxy};
+use std::io::Write;
+use std::num::NonZeroUsize;
+use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 
+/// Number of GET responses kept by the response cache
+const RESPONSE_CACHE_SIZE: usize = 100;
+
+/// Cached responses with the instant they expire
+type ResponseCache = LruCache<String, (HttpResponse, Instant)>;
+
 /// Response from an HTTP request
 #[derive(Debug, Clone)]
 pub struct HttpResponse {
@@ -13,16 +27,124 @@ pub struct HttpResponse {
     pub body: String,
     pub duration_ms: u64,
     pub size_bytes: usize,
+    /// Original bytes for binary bodies, which `body` only holds lossily
+    pub raw_bytes: Option<Vec<u8>>,
+    /// Original bytes of a text body decoded from a charset other than UTF-8
+    /// (or re-decoded by the user), kept so it can be decoded again
+    pub original_bytes: Option<Vec<u8>>,
+    /// `body` holds JSON decoded from a MessagePack response
+    pub decoded_msgpack: bool,
+    /// Header and payload of an `application/jwt` body, decoded for display
+    pub decoded_jwt: Option<String>,
+    /// Response to a HEAD request (no body expected)
+    pub is_head: bool,
+    /// Charset the body was decoded from, when declared in Content-Type or guessed
+    pub detected_charset: Option<String>,
+    /// Served from the response cache instead of the network
+    pub cached: bool,
+    /// Time spent in each phase of the request
+    pub timing: TimingBreakdown,
 }
 
 impl HttpResponse {
+    /// How long to wait before sending again when the server says the rate limit is
+    /// used up (X-RateLimit-Remaining: 0, or a 429 with Retry-After)
+    pub fn rate_limit_wait(&self) -> Option<Duration> {
+        let header = |name: &str| {
+            self.headers
+                .iter()
+                .find(|(key, _)| key.eq_ignore_ascii_case(name))
+                .map(|(_, value)| value.trim())
+        };
+
+        let exhausted = header("x-ratelimit-remaining")
+            .and_then(|remaining| remaining.parse::<u64>().ok())
+            == Some(0);
+        if !exhausted && self.status != 429 {
+            return None;
+        }
+
+        if let Some(seconds) = header("retry-after").and_then(|value| value.parse::<u64>().ok()) {
+            return Some(Duration::from_secs(seconds));
+        }
+        // Some APIs send a Unix timestamp, others the seconds left in the window
+        let reset = header("x-ratelimit-reset")?.parse::<u64>().ok()?;
+        if reset >= 1_000_000_000 {
+            let now = chrono::Utc::now().timestamp().max(0) as u64;
+            Some(Duration::from_secs(reset.saturating_sub(now)))
+        } else {
+            Some(Duration::from_secs(reset))
+        }
+    }
+
+    /// Whether the response declares an HTML Content-Type
+    pub fn is_html(&self) -> bool {
+        self.headers.iter().any(|(k, v)| {
+            k.eq_ignore_ascii_case("content-type") && v.to_lowercase().starts_with("text/html")
+        })
+    }
+
     /// Try to format the body as pretty JSON
     pub fn pretty_body(&self) -> String {
-        if let Ok(json) = serde_json::from_str::<serde_json::Value>(&self.body) {
-            serde_json::to_string_pretty(&json).unwrap_or_else(|_| self.body.clone())
-        } else {
-            self.body.clone()
+        self.pretty_body_with_indent(2)
+    }
+
+    /// JSON bodies re-indented with `indent` spaces; other bodies unchanged
+    pub fn pretty_body_with_indent(&self, indent: u8) -> String {
+        let Ok(json) = serde_json::from_str::<serde_json::Value>(&self.body) else {
+            return self.body.clone();
+        };
+        let indent = " ".repeat(indent as usize);
+        let formatter = serde_json::ser::PrettyFormatter::with_indent(indent.as_bytes());
+        let mut out = Vec::new();
+        let mut serializer = serde_json::Serializer::with_formatter(&mut out, formatter);
+        match serde::Serialize::serialize(&json, &mut serializer) {
+            Ok(()) => String::from_utf8(out).unwrap_or_else(|_| self.body.clone()),
+            Err(_) => self.body.clone(),
+        }
+    }
+
+    /// Hex dump of the raw body (16 bytes per line with an ASCII column), if binary
+    pub fn hex_dump(&self) -> Option<Vec<String>> {
+        let bytes = self.raw_bytes.as_ref()?;
+        Some(
+            bytes
+                .chunks(16)
+                .enumerate()
+                .map(|(i, chunk)| {
+                    let hex: Vec<String> = chunk.iter().map(|b| format!("{:02x}", b)).collect();
+                    let ascii: String = chunk
+                        .iter()
+                        .map(|&b| {
+                            if b.is_ascii_graphic() || b == b' ' {
+                                b as char
+                            } else {
+                                '.'
+                            }
+                        })
+                        .collect();
+                    format!("{:08x}  {:<47}  |{}|", i * 16, hex.join(" "), ascii)
+                })
+                .collect(),
+        )
+    }
+
+    /// Re-decode the original bytes with another encoding. Text bodies keep their
+    /// bytes in `original_bytes` so the body can be decoded again later.
+    pub fn redecode(&mut self, encoding: ResponseEncoding) {
+        if self.decoded_msgpack {
+            return;
+        }
+        if let Some(bytes) = &self.raw_bytes {
+            self.body = encoding.decode(bytes);
+            return;
         }
+        let bytes = self
+            .original_bytes
+            .take()
+            .unwrap_or_else(|| std::mem::take(&mut self.body).into_bytes());
+        self.body = encoding.decode(&bytes);
+        self.original_bytes = Some(bytes);
     }
 
     /// Check if the response is successful (2xx)
@@ -31,16 +153,131 @@ impl HttpResponse {
     }
 }
 
+/// Encodings offered when a body was sent with the wrong charset
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResponseEncoding {
+    Utf8,
+    Latin1,
+    Windows1252,
+    ShiftJis,
+    Gbk,
+}
+
+impl ResponseEncoding {
+    pub const ALL: [ResponseEncoding; 5] = [
+        ResponseEncoding::Utf8,
+        ResponseEncoding::Latin1,
+        ResponseEncoding::Windows1252,
+        ResponseEncoding::ShiftJis,
+        ResponseEncoding::Gbk,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ResponseEncoding::Utf8 => "UTF-8",
+            ResponseEncoding::Latin1 => "Latin-1",
+            ResponseEncoding::Windows1252 => "Windows-1252",
+            ResponseEncoding::ShiftJis => "Shift-JIS",
+            ResponseEncoding::Gbk => "GBK",
+        }
+    }
+
+    pub fn decode(&self, bytes: &[u8]) -> String {
+        let encoding = match self {
+            // encoding_rs treats the latin1 label as windows-1252, so map bytes directly
+            ResponseEncoding::Latin1 => return bytes.iter().map(|&b| b as char).collect(),
+            ResponseEncoding::Utf8 => encoding_rs::UTF_8,
+            ResponseEncoding::Windows1252 => encoding_rs::WINDOWS_1252,
+            ResponseEncoding::ShiftJis => encoding_rs::SHIFT_JIS,
+            ResponseEncoding::Gbk => encoding_rs::GBK,
+        };
+        encoding.decode_without_bom_handling(bytes).0.into_owned()
+    }
+}
+
+/// Request state sent from the background task while a response downloads
+#[derive(Debug, Clone)]
+pub enum Progress {
+    InProgress {
+        received: usize,
+        total: Option<usize>,
+    },
+    /// Errors are sent as text since `anyhow::Error` is not `Clone`; boxed to
+    /// keep the progress updates small
+    Done(Box<Result<HttpResponse, String>>),
+}
+
 /// HTTP client wrapper
 #[derive(Clone)]
 pub struct HttpClient {
     client: Client,
+    /// Kept to build one-off clients for requests with a proxy override
+    settings: Settings,
+    /// GET response cache, present when `cache_enabled` is set
+    cache: Option<Arc<Mutex<ResponseCache>>>,
 }
 
 impl HttpClient {
     pub fn new() -> Result<Self> {
-        let client = Client::builder().timeout(Duration::from_secs(30)).build()?;
-        Ok(Self { client })
+        Self::with_settings(&Settings::default())
+    }
+
+    /// Build a client honoring the timeout, redirect and TLS settings
+    pub fn with_settings(settings: &Settings) -> Result<Self> {
+        let client = Self::build_client(settings, None)?;
+        let cache = settings.cache_enabled.then(|| {
+            let size = NonZeroUsize::new(RESPONSE_CACHE_SIZE).expect("cache size is non-zero");
+            Arc::new(Mutex::new(LruCache::new(size)))
+        });
+        Ok(Self {
+            client,
+            settings: settings.clone(),
+            cache,
+        })
+    }
+
+    /// Build a reqwest client; `proxy` replaces the environment's proxies (`"none"` disables them)
+    fn build_client(settings: &Settings, proxy: Option<&str>) -> Result<Client> {
+        let redirect = if settings.follow_redirects {
+            Policy::default()
+        } else {
+            Policy::none()
+        };
+        let mut builder = Client::builder()
+            .timeout(Duration::from_millis(settings.default_timeout_ms))
+            .redirect(redirect)
+            .use_preconfigured_tls(timing::tls_config(settings.verify_ssl))
+            .dns_resolver(Arc::new(TimingResolver))
+            .connector_layer(TimingLayer);
+        match proxy {
+            Some(proxy) if proxy.eq_ignore_ascii_case("none") => builder = builder.no_proxy(),
+            Some(proxy) => builder = builder.no_proxy().proxy(Proxy::all(proxy)?),
+            None => {}
+        }
+        Ok(builder.build()?)
+    }
+
+    /// Drop every cached response
+    pub fn clear_cache(&self) -> usize {
+        let Some(cache) = &self.cache else {
+            return 0;
+        };
+        let mut cache = cache.lock().unwrap_or_else(|err| err.into_inner());
+        let count = cache.len();
+        cache.clear();
+        count
+    }
+
+    fn cached_response(&self, key: &str) -> Option<HttpResponse> {
+        let mut cache = self.cache.as_ref()?.lock().ok()?;
+        let (response, expires) = cache.get(key)?;
+        if *expires <= Instant::now() {
+            cache.pop(key);
+            return None;
+        }
+        let mut response = response.clone();
+        response.cached = true;
+        Some(response)
     }
 
     /// Execute an API request
@@ -49,6 +286,222 @@ impl HttpClient {
         request: &ApiRequest,
         interpolate: impl Fn(&str) -> String,
     ) -> Result<HttpResponse> {
+        self.execute_with_progress(request, interpolate, |_, _| {})
+            .await
+    }
+
+    /// Execute an API request, reporting bytes received (and the expected total,
+    /// when the server sends Content-Length) as the body downloads
+    pub async fn execute_with_progress(
+        &self,
+        request: &ApiRequest,
+        interpolate: impl Fn(&str) -> String,
+        on_progress: impl Fn(usize, Option<usize>),
+    ) -> Result<HttpResponse> {
+        let builder = self.build_request(request, &interpolate)?;
+        if tracing::enabled!(tracing::Level::DEBUG) {
+            if let Some(built) = builder.try_clone().and_then(|b| b.build().ok()) {
+                let headers: Vec<(String, String)> = built
+                    .headers()
+                    .iter()
+                    .map(|(k, v)| (k.to_string(), v.to_str().unwrap_or("<binary>").to_string()))
+                    .collect();
+                // An API key sent in the query string is masked like the auth headers
+                let api_key_param = if request.auth.auth_type == AuthType::ApiKey
+                    && request.auth.api_key_location == "query"
+                {
+                    interpolate(&request.auth.api_key_name)
+                } else {
+                    String::new()
+                };
+                tracing::debug!(
+                    method = %built.method(),
+                    url = %mask_query_param(built.url(), &api_key_param),
+                    headers = ?mask_sensitive_headers(&headers),
+                    body_bytes = built.body().and_then(|b| b.as_bytes()).map_or(0, <[u8]>::len),
+                    "Sending request"
+                );
+            }
+        }
+
+        // Only GETs are cached, keyed by the final URL and headers
+        let cache_key = self
+            .cache
+            .as_ref()
+            .filter(|_| request.method == HttpMethod::Get)
+            .and_then(|_| builder.try_clone()?.build().ok())
+            .map(|built| cache_key(&built));
+        if let Some(response) = cache_key
+            .as_deref()
+            .and_then(|key| self.cached_response(key))
+        {
+            return Ok(response);
+        }
+
+        // Execute the request
+        let (response, times) = timing::timed_send(builder.send()).await;
+        let mut response = response?;
+        let duration_ms = times.elapsed.as_millis() as u64;
+
+        // Parse response
+        let status = response.status().as_u16();
+        let status_text = response
+            .status()
+            .canonical_reason()
+            .unwrap_or("")
+            .to_string();
+        let headers: Vec<(String, String)> = response
+            .headers()
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_str().unwrap_or("").to_string()))
+            .collect();
+
+        let content_type = headers
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case("content-type"))
+            .map(|(_, v)| v.to_lowercase())
+            .unwrap_or_default();
+
+        // Read the body in chunks so callers can show download progress
+        let total = response.content_length().map(|len| len as usize);
+        let mut bytes = Vec::with_capacity(total.unwrap_or(0));
+        let transfer_start = Instant::now();
+        while let Some(chunk) = response.chunk().await? {
+            bytes.extend_from_slice(&chunk);
+            on_progress(bytes.len(), total);
+        }
+        let size_bytes = bytes.len();
+        tracing::debug!(status, duration_ms, size_bytes, "Received response");
+        // The connector call covers DNS and TLS as well, so connect is what remains of it
+        let timing = TimingBreakdown {
+            dns_ms: times.dns.as_millis() as u64,
+            connect_ms: times
+                .connect
+                .saturating_sub(times.dns + times.tls)
+                .as_millis() as u64,
+            tls_ms: times.tls.as_millis() as u64,
+            ttfb_ms: times
+                .elapsed
+                .saturating_sub(times.connect.max(times.dns))
+                .as_millis() as u64,
+            transfer_ms: transfer_start.elapsed().as_millis() as u64,
+        };
+
+        let mut detected_charset = charset_param(&content_type);
+        let mut decoded_msgpack = false;
+        let mut original_bytes = None;
+        let (body, raw_bytes) = if is_binary_content_type(&content_type) {
+            let body = if is_msgpack_content_type(&content_type) {
+                match rmp_serde::from_slice::<serde_json::Value>(&bytes) {
+                    Ok(value) => {
+                        decoded_msgpack = true;
+                        value.to_string()
+                    }
+                    Err(_) => String::from_utf8_lossy(&bytes).into_owned(),
+                }
+            } else {
+                String::from_utf8_lossy(&bytes).into_owned()
+            };
+            (body, Some(bytes))
+        } else {
+            let declared = detected_charset
+                .as_deref()
+                .and_then(|label| encoding_rs::Encoding::for_label(label.as_bytes()))
+                .filter(|encoding| *encoding != encoding_rs::UTF_8);
+            if let Some(encoding) = declared {
+                let body = encoding.decode_without_bom_handling(&bytes).0.into_owned();
+                original_bytes = Some(bytes);
+                (body, None)
+            } else {
+                match String::from_utf8(bytes) {
+                    Ok(body) => (body, None),
+                    // Mislabelled text: fall back to windows-1252, the usual culprit
+                    Err(err) if is_text_content_type(&content_type) => {
+                        let bytes = err.into_bytes();
+                        detected_charset = Some("windows-1252".to_string());
+                        let body = ResponseEncoding::Windows1252.decode(&bytes);
+                        original_bytes = Some(bytes);
+                        (body, None)
+                    }
+                    // Undeclared binary content: keep the bytes for the hex view
+                    Err(err) => {
+                        let bytes = err.into_bytes();
+                        (String::from_utf8_lossy(&bytes).into_owned(), Some(bytes))
+                    }
+                }
+            }
+        };
+
+        let decoded_jwt = content_type
+            .starts_with("application/jwt")
+            .then(|| crate::jwt::decode_jwt(&body))
+            .flatten()
+            .map(|jwt| jwt.to_sections());
+
+        let response = HttpResponse {
+            status,
+            status_text,
+            headers,
+            body,
+            duration_ms,
+            size_bytes,
+            raw_bytes,
+            original_bytes,
+            decoded_msgpack,
+            decoded_jwt,
+            is_head: request.method == HttpMethod::Head,
+            detected_charset,
+            cached: false,
+            timing,
+        };
+
+        let ttl = request
+            .cache_ttl_override_ms
+            .map(Duration::from_millis)
+            .or_else(|| cache_ttl(&response.headers))
+            .filter(|ttl| !ttl.is_zero());
+        if let (Some(key), Some(ttl), Some(cache)) = (cache_key, ttl, &self.cache) {
+            if let Ok(mut cache) = cache.lock() {
+                cache.put(key, (response.clone(), Instant::now() + ttl));
+            }
+        }
+
+        Ok(response)
+    }
+
+    /// Render the request line and headers exactly as they will be sent, curl -v style
+    pub fn preview(
+        &self,
+        request: &ApiRequest,
+        interpolate: impl Fn(&str) -> String,
+    ) -> Result<Vec<String>> {
+        let built = self.build(request, interpolate)?;
+
+        let mut lines = vec![format!("> {} {}", built.method(), built.url())];
+        for (key, value) in built.headers() {
+            lines.push(format!(
+                "> {}: {}",
+                key,
+                value.to_str().unwrap_or("<binary>")
+            ));
+        }
+        Ok(lines)
+    }
+
+    /// The request as it will be sent, without sending it
+    pub fn build(
+        &self,
+        request: &ApiRequest,
+        interpolate: impl Fn(&str) -> String,
+    ) -> Result<reqwest::Request> {
+        Ok(self.build_request(request, &interpolate)?.build()?)
+    }
+
+    fn build_request(
+        &self,
+        request: &ApiRequest,
+        interpolate: &impl Fn(&str) -> String,
+    ) -> Result<reqwest::RequestBuilder> {
         let url = interpolate(&request.url);
         let method = match request.method {
             HttpMethod::Get => Method::GET,
@@ -56,9 +509,20 @@ impl HttpClient {
             HttpMethod::Put => Method::PUT,
             HttpMethod::Patch => Method::PATCH,
             HttpMethod::Delete => Method::DELETE,
+            HttpMethod::Head => Method::HEAD,
         };
 
-        let mut builder = self.client.request(method, &url);
+        // A proxy override needs its own client, since proxies are set per client
+        let proxy = request
+            .proxy_override
+            .as_deref()
+            .map(interpolate)
+            .filter(|proxy| !proxy.trim().is_empty());
+        let mut builder = match proxy {
+            Some(proxy) => Self::build_client(&self.settings, Some(proxy.trim()))?,
+            None => self.client.clone(),
+        }
+        .request(method, &url);
 
         // Add query parameters
         let query_params: Vec<(String, String)> = request
@@ -71,55 +535,45 @@ impl HttpClient {
             builder = builder.query(&query_params);
         }
 
+        let sends_body = matches!(
+            request.method,
+            HttpMethod::Post | HttpMethod::Put | HttpMethod::Patch
+        ) && !request.body.is_empty();
+        let content_type = request.sent_content_type().filter(|_| sends_body);
+
         // Add headers
         for header in &request.headers {
             if header.enabled && !header.key.is_empty() {
-                builder = builder.header(interpolate(&header.key), interpolate(&header.value));
+                let key = interpolate(&header.key);
+                if content_type.is_some() && key.eq_ignore_ascii_case("content-type") {
+                    continue;
+                }
+                builder = builder.header(key, interpolate(&header.value));
             }
         }
+        if let Some(content_type) = content_type {
+            builder = builder.header("Content-Type", content_type);
+        }
 
         // Add authentication
-        builder = self.apply_auth(builder, &request.auth, &interpolate);
+        builder = self.apply_auth(builder, &request.auth, interpolate);
 
         // Add body for POST/PUT/PATCH
-        if matches!(
-            request.method,
-            HttpMethod::Post | HttpMethod::Put | HttpMethod::Patch
-        ) && !request.body.is_empty()
-        {
-            let body = interpolate(&request.body);
-            builder = builder.body(body);
+        if sends_body {
+            let body = request.encoded_body(interpolate)?;
+            builder = match request.compress_body {
+                Some(compression) => match compress(body.as_bytes(), compression) {
+                    Ok(bytes) => builder
+                        .header("Content-Encoding", compression.as_str())
+                        .body(bytes),
+                    // In-memory compression only fails on allocation errors; send uncompressed
+                    Err(_) => builder.body(body),
+                },
+                None => builder.body(body),
+            };
         }
 
-        // Execute the request
-        let start = Instant::now();
-        let response = builder.send().await?;
-        let duration_ms = start.elapsed().as_millis() as u64;
-
-        // Parse response
-        let status = response.status().as_u16();
-        let status_text = response
-            .status()
-            .canonical_reason()
-            .unwrap_or("")
-            .to_string();
-        let headers: Vec<(String, String)> = response
-            .headers()
-            .iter()
-            .map(|(k, v)| (k.to_string(), v.to_str().unwrap_or("").to_string()))
-            .collect();
-
-        let body = response.text().await?;
-        let size_bytes = body.len();
-
-        Ok(HttpResponse {
-            status,
-            status_text,
-            headers,
-            body,
-            duration_ms,
-            size_bytes,
-        })
+        Ok(builder)
     }
 
     fn apply_auth(
@@ -134,6 +588,11 @@ impl HttpClient {
                 let token = interpolate(&auth.bearer_token);
                 builder.header("Authorization", format!("Bearer {}", token))
             }
+            AuthType::OAuth2Pkce if !auth.oauth2.access_token.is_empty() => builder.header(
+                "Authorization",
+                format!("Bearer {}", auth.oauth2.access_token),
+            ),
+            AuthType::OAuth2Pkce => builder,
             AuthType::Basic => {
                 let username = interpolate(&auth.basic_username);
                 let password = interpolate(&auth.basic_password);
@@ -154,8 +613,250 @@ impl HttpClient {
     }
 }
 
+fn compress(bytes: &[u8], compression: CompressionType) -> std::io::Result<Vec<u8>> {
+    match compression {
+        CompressionType::Gzip => {
+            let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+            encoder.write_all(bytes)?;
+            encoder.finish()
+        }
+        // HTTP "deflate" is the zlib format
+        CompressionType::Deflate => {
+            let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+            encoder.write_all(bytes)?;
+            encoder.finish()
+        }
+    }
+}
+
+/// Copy of `headers` with credentials replaced, safe to write to a log
+pub fn mask_sensitive_headers(headers: &[(String, String)]) -> Vec<(String, String)> {
+    headers
+        .iter()
+        .map(|(key, value)| {
+            let sensitive =
+                key.eq_ignore_ascii_case("authorization") || key.eq_ignore_ascii_case("x-api-key");
+            let value = if sensitive {
+                "[REDACTED]"
+            } else {
+                value.as_str()
+            };
+            (key.clone(), value.to_string())
+        })
+        .collect()
+}
+
+/// `url` with the value of its `name` query parameter replaced, safe to write to a log
+pub fn mask_query_param(url: &reqwest::Url, name: &str) -> String {
+    let Some(query) = url.query().filter(|_| !name.is_empty()) else {
+        return url.to_string();
+    };
+    let query: Vec<String> = query
+        .split('&')
+        .map(|pair| {
+            let key = pair.split('=').next().unwrap_or_default();
+            if percent_decode_str(&key.replace('+', " ")).decode_utf8_lossy() == name {
+                format!("{}=[REDACTED]", key)
+            } else {
+                pair.to_string()
+            }
+        })
+        .collect();
+    let mut masked = url.clone();
+    masked.set_query(Some(&query.join("&")));
+    masked.to_string()
+}
+
+fn cache_key(request: &reqwest::Request) -> String {
+    let mut headers: Vec<String> = request
+        .headers()
+        .iter()
+        .map(|(key, value)| format!("{}: {}", key, value.to_str().unwrap_or_default()))
+        .collect();
+    headers.sort();
+    format!("{}\n{}", request.url(), headers.join("\n"))
+}
+
+/// How long a response may be cached, from Cache-Control max-age or Expires
+fn cache_ttl(headers: &[(String, String)]) -> Option<Duration> {
+    let header = |name: &str| {
+        headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    };
+
+    if let Some(cache_control) = header("cache-control") {
+        for directive in cache_control.split(',').map(str::trim) {
+            if directive.eq_ignore_ascii_case("no-store")
+                || directive.eq_ignore_ascii_case("no-cache")
+            {
+                return Some(Duration::ZERO);
+            }
+            if let Some(seconds) = directive.strip_prefix("max-age=") {
+                return seconds.trim().parse().ok().map(Duration::from_secs);
+            }
+        }
+    }
+
+    let expires = chrono::DateTime::parse_from_rfc2822(header("expires")?).ok()?;
+    let now = header("date")
+        .and_then(|date| chrono::DateTime::parse_from_rfc2822(date).ok())
+        .map(|date| date.with_timezone(&chrono::Utc))
+        .unwrap_or_else(chrono::Utc::now);
+    Some(
+        (expires.with_timezone(&chrono::Utc) - now)
+            .to_std()
+            .unwrap_or(Duration::ZERO),
+    )
+}
+
+/// The `charset` parameter of a Content-Type value, lowercased
+fn charset_param(content_type: &str) -> Option<String> {
+    content_type.split(';').skip(1).find_map(|param| {
+        let (name, value) = param.split_once('=')?;
+        (name.trim() == "charset").then(|| value.trim().trim_matches('"').to_lowercase())
+    })
+}
+
+fn is_text_content_type(content_type: &str) -> bool {
+    content_type.starts_with("text/")
+        || ["json", "xml", "javascript", "html"]
+            .iter()
+            .any(|ty| content_type.contains(ty))
+}
+
+fn is_msgpack_content_type(content_type: &str) -> bool {
+    content_type.starts_with("application/msgpack")
+        || content_type.starts_with("application/x-msgpack")
+}
+
+/// Content types whose bodies should be kept as raw bytes
+fn is_binary_content_type(content_type: &str) -> bool {
+    is_msgpack_content_type(content_type)
+        || [
+            "application/octet-stream",
+            "application/pdf",
+            "application/zip",
+        ]
+        .iter()
+        .any(|ty| content_type.starts_with(ty))
+        || ["image/", "audio/", "video/"]
+            .iter()
+            .any(|ty| content_type.starts_with(ty))
+}
+
 impl Default for HttpClient {
     fn default() -> Self {
         Self::new().expect("Failed to create HTTP client")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    fn response(status: u16, pairs: &[(&str, &str)]) -> HttpResponse {
+        HttpResponse {
+            status,
+            status_text: String::new(),
+            headers: headers(pairs),
+            body: String::new(),
+            duration_ms: 0,
+            size_bytes: 0,
+            raw_bytes: None,
+            original_bytes: None,
+            decoded_msgpack: false,
+            decoded_jwt: None,
+            is_head: false,
+            detected_charset: None,
+            cached: false,
+            timing: TimingBreakdown::default(),
+        }
+    }
+
+    #[test]
+    fn test_rate_limit_wait() {
+        let wait = |status: u16, pairs: &[(&str, &str)]| response(status, pairs).rate_limit_wait();
+
+        assert_eq!(
+            wait(429, &[("Retry-After", " 30 ")]),
+            Some(Duration::from_secs(30))
+        );
+        assert_eq!(
+            wait(
+                200,
+                &[("X-RateLimit-Remaining", "0"), ("X-RateLimit-Reset", "12")]
+            ),
+            Some(Duration::from_secs(12))
+        );
+        // A reset timestamp in the past means the window is already open
+        assert_eq!(
+            wait(
+                200,
+                &[
+                    ("x-ratelimit-remaining", "0"),
+                    ("x-ratelimit-reset", "1000000000")
+                ]
+            ),
+            Some(Duration::ZERO)
+        );
+        // Retry-After only counts when the limit is actually used up
+        assert_eq!(wait(200, &[("Retry-After", "30")]), None);
+        assert_eq!(wait(200, &[("X-RateLimit-Remaining", "5")]), None);
+        // A 429 with a missing or malformed Retry-After and no reset header
+        assert_eq!(wait(429, &[]), None);
+        assert_eq!(
+            wait(429, &[("Retry-After", "Fri, 16 Oct 2026 12:00:00 GMT")]),
+            None
+        );
+        // Malformed Retry-After falls back to the reset header
+        assert_eq!(
+            wait(429, &[("Retry-After", "soon"), ("X-RateLimit-Reset", "7")]),
+            Some(Duration::from_secs(7))
+        );
+    }
+
+    #[test]
+    fn test_cache_ttl() {
+        let ttl = |pairs: &[(&str, &str)]| cache_ttl(&headers(pairs));
+
+        assert_eq!(
+            ttl(&[("Cache-Control", "public, max-age=60")]),
+            Some(Duration::from_secs(60))
+        );
+        assert_eq!(
+            ttl(&[("cache-control", "no-store, max-age=60")]),
+            Some(Duration::ZERO)
+        );
+        assert_eq!(ttl(&[("Cache-Control", "No-Cache")]), Some(Duration::ZERO));
+        // Bad units or values are not guessed at
+        assert_eq!(ttl(&[("Cache-Control", "max-age=60s")]), None);
+        assert_eq!(ttl(&[("Cache-Control", "max-age=abc")]), None);
+        assert_eq!(ttl(&[("Cache-Control", "max-age=-5")]), None);
+
+        assert_eq!(
+            ttl(&[
+                ("Date", "Fri, 16 Oct 2026 12:00:00 GMT"),
+                ("Expires", "Fri, 16 Oct 2026 12:05:00 GMT"),
+            ]),
+            Some(Duration::from_secs(300))
+        );
+        assert_eq!(
+            ttl(&[
+                ("Date", "Fri, 16 Oct 2026 12:00:00 GMT"),
+                ("Expires", "Fri, 16 Oct 2026 11:00:00 GMT"),
+            ]),
+            Some(Duration::ZERO)
+        );
+        assert_eq!(ttl(&[("Expires", "0")]), None);
+        assert_eq!(ttl(&[("Content-Type", "application/json")]), None);
+    }
+}
@@ -0,0 +1,80 @@
+use regex::{Captures, Regex};
+
+/// Render an HTML document as plain text for reading in the terminal.
+///
+/// This is deliberately not a parser: scripts, styles and comments are dropped,
+/// block-level tags become line breaks, every other tag is stripped, and common
+/// entities are decoded. Blank lines are collapsed so the result stays compact.
+pub fn html_to_text(html: &str) -> String {
+    let hidden =
+        Regex::new(r"(?is)<script\b.*?</script\s*>|<style\b.*?</style\s*>|<!--.*?-->").unwrap();
+    let breaks = Regex::new(r"(?i)<(?:br|/?p|/?div|/?li|/?tr|/?h[1-6]|/title)\b[^>]*>").unwrap();
+    let tags = Regex::new(r"<[^>]+>").unwrap();
+
+    let text = hidden.replace_all(html, "");
+    let text = breaks.replace_all(&text, "\n");
+    let text = tags.replace_all(&text, "");
+    let text = decode_entities(&text);
+
+    let mut lines: Vec<&str> = Vec::new();
+    for line in text.lines().map(str::trim) {
+        if line.is_empty() && lines.last().is_none_or(|last| last.is_empty()) {
+            continue;
+        }
+        lines.push(line);
+    }
+    while lines.last().is_some_and(|last| last.is_empty()) {
+        lines.pop();
+    }
+    lines.join("\n")
+}
+
+/// Decode named (`&amp;`, `&lt;`, `&gt;`, `&nbsp;`, `&quot;`, `&apos;`) and numeric entities
+fn decode_entities(text: &str) -> String {
+    let entity = Regex::new(r"(?i)&(#x[0-9a-f]+|#[0-9]+|amp|lt|gt|nbsp|quot|apos);").unwrap();
+    entity
+        .replace_all(text, |caps: &Captures| {
+            let name = caps[1].to_ascii_lowercase();
+            let decoded = match name.as_str() {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "nbsp" => Some(' '),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                _ => name
+                    .strip_prefix("#x")
+                    .map(|hex| u32::from_str_radix(hex, 16))
+                    .unwrap_or_else(|| name[1..].parse())
+                    .ok()
+                    .and_then(char::from_u32),
+            };
+            decoded.map_or_else(|| caps[0].to_string(), String::from)
+        })
+        .into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_html_to_text() {
+        let html = r#"<html><head><title>Home</title><style>p { color: red }</style></head>
+<body>
+  <h1>Hello &amp; welcome</h1>
+  <!-- hidden -->
+  <p>Use <code>&lt;b&gt;</code> for&nbsp;bold &#169; &#x263A;</p>
+  <script>alert("hi")</script>
+</body></html>"#;
+        assert_eq!(
+            html_to_text(html),
+            "Home\n\nHello & welcome\n\nUse <b> for bold © ☺"
+        );
+    }
+
+    #[test]
+    fn test_unknown_entities_are_kept() {
+        assert_eq!(decode_entities("a &copy; b &#xZZ;"), "a &copy; b &#xZZ;");
+    }
+}
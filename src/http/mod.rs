@@ -1,3 +1,7 @@
 pub mod client;
+pub mod format;
+pub mod subscription;
+pub mod timing;
 
-pub use client::{HttpClient, HttpResponse};
+pub use client::{HttpClient, HttpResponse, Progress, ResponseEncoding};
+pub use format::html_to_text;
@@ -0,0 +1,234 @@
+use anyhow::{anyhow, bail, Result};
+use futures_util::{SinkExt, StreamExt};
+use serde_json::{json, Value};
+use tokio::sync::{mpsc, oneshot};
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::http::{header, HeaderValue};
+use tokio_tungstenite::tungstenite::Message;
+
+/// WebSocket subprotocol of the graphql-ws library's protocol
+pub const SUBPROTOCOL: &str = "graphql-transport-ws";
+
+/// Only one operation runs per connection
+const OPERATION_ID: &str = "1";
+
+/// Progress of a running subscription, sent to the app as it happens
+#[derive(Debug, Clone, PartialEq)]
+pub enum SubscriptionEvent {
+    /// The server acknowledged the connection and the operation was sent
+    Connected,
+    /// Payload of a `next` message, pretty-printed
+    Next(String),
+    /// The connection ended, with the reason when it was not a normal close
+    Closed(Option<String>),
+}
+
+/// The GraphQL operation a request body holds, as a `subscribe` payload. The
+/// body is either the query text or a JSON object with `query` (and `variables`).
+pub fn subscription_payload(body: &str) -> Value {
+    match serde_json::from_str::<Value>(body) {
+        Ok(payload) if payload.get("query").is_some_and(Value::is_string) => payload,
+        _ => json!({ "query": body }),
+    }
+}
+
+/// Whether a request body holds a GraphQL subscription operation
+pub fn is_subscription(body: &str) -> bool {
+    subscription_payload(body)["query"]
+        .as_str()
+        .is_some_and(|query| query.trim_start().starts_with("subscription"))
+}
+
+/// `url` with its scheme switched to the WebSocket one (http -> ws, https -> wss)
+pub fn websocket_url(url: &reqwest::Url) -> Result<reqwest::Url> {
+    let scheme = match url.scheme() {
+        "http" | "ws" => "ws",
+        "https" | "wss" => "wss",
+        other => bail!("Unsupported scheme for a subscription: {}", other),
+    };
+    let mut url = url.clone();
+    url.set_scheme(scheme)
+        .map_err(|_| anyhow!("Invalid WebSocket URL: {}", url))?;
+    Ok(url)
+}
+
+/// A graphql-ws protocol message as (type, payload)
+fn parse_message(text: &str) -> Option<(String, Value)> {
+    let message: Value = serde_json::from_str(text).ok()?;
+    let kind = message.get("type")?.as_str()?.to_string();
+    Some((kind, message.get("payload").cloned().unwrap_or(Value::Null)))
+}
+
+fn text_message(message: Value) -> Message {
+    Message::text(message.to_string())
+}
+
+/// Connect to the request's URL over WebSocket, subscribe with `payload` and
+/// report events until the server completes, the connection drops or `stop`
+/// fires (or its sender is dropped), in which case `complete` is sent first.
+pub async fn run(
+    request: reqwest::Request,
+    payload: Value,
+    events: mpsc::UnboundedSender<SubscriptionEvent>,
+    stop: oneshot::Receiver<()>,
+) {
+    let result = subscribe(request, payload, &events, stop).await;
+    let _ = events.send(SubscriptionEvent::Closed(
+        result.err().map(|e| e.to_string()),
+    ));
+}
+
+async fn subscribe(
+    request: reqwest::Request,
+    payload: Value,
+    events: &mpsc::UnboundedSender<SubscriptionEvent>,
+    mut stop: oneshot::Receiver<()>,
+) -> Result<()> {
+    let url = websocket_url(request.url())?;
+    let mut ws_request = url.as_str().into_client_request()?;
+    // Carry over the request's headers (auth included), except the ones that
+    // describe an HTTP body or belong to the WebSocket handshake
+    for (name, value) in request.headers() {
+        if name == header::CONTENT_TYPE
+            || name == header::CONTENT_LENGTH
+            || name == header::CONTENT_ENCODING
+            || name == header::HOST
+            || name.as_str().starts_with("sec-websocket-")
+        {
+            continue;
+        }
+        ws_request.headers_mut().insert(name.clone(), value.clone());
+    }
+    ws_request.headers_mut().insert(
+        header::SEC_WEBSOCKET_PROTOCOL,
+        HeaderValue::from_static(SUBPROTOCOL),
+    );
+
+    let (mut socket, _) = tokio_tungstenite::connect_async(ws_request).await?;
+    socket
+        .send(text_message(
+            json!({ "type": "connection_init", "payload": {} }),
+        ))
+        .await?;
+
+    // Wait for the server to accept the connection
+    loop {
+        match socket.next().await {
+            Some(Ok(Message::Text(text))) => match parse_message(text.as_str()) {
+                Some((kind, _)) if kind == "connection_ack" => break,
+                Some((kind, _)) if kind == "ping" => {
+                    socket.send(text_message(json!({ "type": "pong" }))).await?;
+                }
+                _ => {}
+            },
+            Some(Ok(Message::Close(frame))) => {
+                let reason = frame.map(|frame| frame.reason.to_string());
+                bail!(
+                    "Connection closed before it was acknowledged{}",
+                    reason
+                        .filter(|reason| !reason.is_empty())
+                        .map(|reason| format!(": {}", reason))
+                        .unwrap_or_default()
+                );
+            }
+            Some(Ok(_)) => {}
+            Some(Err(e)) => return Err(e.into()),
+            None => bail!("Connection closed before it was acknowledged"),
+        }
+    }
+
+    socket
+        .send(text_message(json!({
+            "id": OPERATION_ID,
+            "type": "subscribe",
+            "payload": payload,
+        })))
+        .await?;
+    let _ = events.send(SubscriptionEvent::Connected);
+
+    loop {
+        tokio::select! {
+            _ = &mut stop => {
+                socket
+                    .send(text_message(json!({ "id": OPERATION_ID, "type": "complete" })))
+                    .await?;
+                let _ = socket.close(None).await;
+                return Ok(());
+            }
+            message = socket.next() => match message {
+                Some(Ok(Message::Text(text))) => match parse_message(text.as_str()) {
+                    Some((kind, payload)) if kind == "next" => {
+                        let pretty = serde_json::to_string_pretty(&payload).unwrap_or_default();
+                        let _ = events.send(SubscriptionEvent::Next(pretty));
+                    }
+                    Some((kind, payload)) if kind == "error" => {
+                        bail!("{}", serde_json::to_string(&payload).unwrap_or_default());
+                    }
+                    Some((kind, _)) if kind == "complete" => return Ok(()),
+                    Some((kind, _)) if kind == "ping" => {
+                        socket.send(text_message(json!({ "type": "pong" }))).await?;
+                    }
+                    _ => {}
+                },
+                Some(Ok(Message::Close(_))) | None => return Ok(()),
+                Some(Ok(_)) => {}
+                Some(Err(e)) => return Err(e.into()),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_subscription_payload() {
+        let query = "subscription { messageAdded { id text } }";
+        assert!(is_subscription(query));
+        assert!(is_subscription(&format!("\n  {}", query)));
+        assert_eq!(subscription_payload(query), json!({ "query": query }));
+
+        let body = json!({ "query": query, "variables": { "room": 1 } }).to_string();
+        assert!(is_subscription(&body));
+        assert_eq!(subscription_payload(&body)["variables"]["room"], 1);
+
+        assert!(!is_subscription("query { messages { id } }"));
+        assert!(!is_subscription(r#"{"query": "mutation { send }"}"#));
+        assert!(!is_subscription(""));
+    }
+
+    #[test]
+    fn test_websocket_url_and_messages() {
+        let url = |s: &str| reqwest::Url::parse(s).unwrap();
+        assert_eq!(
+            websocket_url(&url("http://localhost:4000/graphql?a=1"))
+                .unwrap()
+                .as_str(),
+            "ws://localhost:4000/graphql?a=1"
+        );
+        assert_eq!(
+            websocket_url(&url("https://api.example.com/graphql"))
+                .unwrap()
+                .as_str(),
+            "wss://api.example.com/graphql"
+        );
+        assert_eq!(
+            websocket_url(&url("wss://api.example.com/graphql"))
+                .unwrap()
+                .as_str(),
+            "wss://api.example.com/graphql"
+        );
+        assert!(websocket_url(&url("ftp://example.com")).is_err());
+
+        assert_eq!(
+            parse_message(r#"{"id":"1","type":"next","payload":{"data":{"n":1}}}"#),
+            Some(("next".to_string(), json!({ "data": { "n": 1 } })))
+        );
+        assert_eq!(
+            parse_message(r#"{"type":"connection_ack"}"#),
+            Some(("connection_ack".to_string(), Value::Null))
+        );
+        assert_eq!(parse_message("not json"), None);
+    }
+}
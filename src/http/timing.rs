@@ -0,0 +1,307 @@
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+use rustls::client::{
+    ClientSessionMemoryCache, ClientSessionStore, Resumption, Tls12ClientSessionValue,
+    Tls13ClientSessionValue,
+};
+use rustls::crypto::CryptoProvider;
+use rustls::pki_types::{CertificateDer, ServerName, UnixTime};
+use rustls::{DigitallySignedStruct, NamedGroup, SignatureScheme};
+use std::future::Future;
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+use tower_layer::Layer;
+use tower_service::Service;
+
+tokio::task_local! {
+    /// Phase durations of the request being sent on this task
+    static PHASES: Arc<Mutex<Phases>>;
+}
+
+#[derive(Debug, Default)]
+struct Phases {
+    dns: Duration,
+    /// Whole connector call: DNS, TCP connect and TLS handshake
+    connect: Duration,
+    /// TLS handshakes, from the ClientHello until the connector returned
+    tls: Duration,
+    /// When rustls started the handshake of the connection being made
+    tls_start: Option<Instant>,
+}
+
+/// Phase durations of one send, as recorded by the resolver, connector and TLS hooks
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SendTimes {
+    pub dns: Duration,
+    /// Connector time (DNS, TCP and TLS together)
+    pub connect: Duration,
+    pub tls: Duration,
+    /// Until `send` finished, i.e. the response headers arrived
+    pub elapsed: Duration,
+}
+
+/// Where the time of a request went, in milliseconds. All zero for cached responses;
+/// DNS, connect and TLS are zero when a pooled connection was reused.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TimingBreakdown {
+    pub dns_ms: u64,
+    /// TCP connect
+    pub connect_ms: u64,
+    /// TLS handshake (zero for plain HTTP)
+    pub tls_ms: u64,
+    /// Waiting for the response headers once connected
+    pub ttfb_ms: u64,
+    /// Reading the response body
+    pub transfer_ms: u64,
+}
+
+impl TimingBreakdown {
+    /// (label, milliseconds) in the order the phases happen
+    pub fn phases(&self) -> [(&'static str, u64); 5] {
+        [
+            ("DNS", self.dns_ms),
+            ("Connect", self.connect_ms),
+            ("TLS", self.tls_ms),
+            ("TTFB", self.ttfb_ms),
+            ("Transfer", self.transfer_ms),
+        ]
+    }
+
+    pub fn total_ms(&self) -> u64 {
+        self.phases().iter().map(|(_, ms)| ms).sum()
+    }
+}
+
+/// Run `send` with phase recording, returning its output and the time spent in each
+/// phase of it
+pub async fn timed_send<F: Future>(send: F) -> (F::Output, SendTimes) {
+    let phases = Arc::new(Mutex::new(Phases::default()));
+    let start = Instant::now();
+    let output = PHASES.scope(Arc::clone(&phases), send).await;
+    let elapsed = start.elapsed();
+    let phases = phases.lock().unwrap_or_else(|err| err.into_inner());
+    let times = SendTimes {
+        dns: phases.dns,
+        connect: phases.connect,
+        tls: phases.tls,
+        elapsed,
+    };
+    (output, times)
+}
+
+fn current_phases() -> Option<Arc<Mutex<Phases>>> {
+    PHASES.try_with(Arc::clone).ok()
+}
+
+/// System resolver that records how long each lookup took
+pub struct TimingResolver;
+
+impl Resolve for TimingResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let phases = current_phases();
+        Box::pin(async move {
+            let start = Instant::now();
+            let addrs: Vec<SocketAddr> =
+                tokio::net::lookup_host((name.as_str(), 0)).await?.collect();
+            if let Some(phases) = phases {
+                phases.lock().unwrap_or_else(|err| err.into_inner()).dns += start.elapsed();
+            }
+            Ok(Box::new(addrs.into_iter()) as Addrs)
+        })
+    }
+}
+
+/// Connector layer that records how long establishing a connection took
+#[derive(Clone)]
+pub struct TimingLayer;
+
+impl<S> Layer<S> for TimingLayer {
+    type Service = TimingConnector<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        TimingConnector { inner }
+    }
+}
+
+#[derive(Clone)]
+pub struct TimingConnector<S> {
+    inner: S,
+}
+
+impl<S, R> Service<R> for TimingConnector<S>
+where
+    S: Service<R>,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<S::Response, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: R) -> Self::Future {
+        let phases = current_phases();
+        let connecting = self.inner.call(request);
+        Box::pin(async move {
+            let start = Instant::now();
+            let result = connecting.await;
+            if let Some(phases) = phases {
+                let mut phases = phases.lock().unwrap_or_else(|err| err.into_inner());
+                phases.connect += start.elapsed();
+                if let Some(tls_start) = phases.tls_start.take() {
+                    phases.tls += tls_start.elapsed();
+                }
+            }
+            result
+        })
+    }
+}
+
+/// rustls config for reqwest, matching the one it builds itself (webpki roots,
+/// HTTP/1.1 ALPN) but with a session store that marks when each handshake starts
+pub fn tls_config(verify_certs: bool) -> rustls::ClientConfig {
+    let provider = CryptoProvider::get_default()
+        .cloned()
+        .unwrap_or_else(|| Arc::new(rustls::crypto::ring::default_provider()));
+    let builder = rustls::ClientConfig::builder_with_provider(provider)
+        .with_safe_default_protocol_versions()
+        .expect("the default provider supports the default TLS versions");
+    let mut config = if verify_certs {
+        let roots = rustls::RootCertStore {
+            roots: webpki_roots::TLS_SERVER_ROOTS.to_vec(),
+        };
+        builder.with_root_certificates(roots).with_no_client_auth()
+    } else {
+        builder
+            .dangerous()
+            .with_custom_certificate_verifier(Arc::new(AcceptAnyCert))
+            .with_no_client_auth()
+    };
+    config.alpn_protocols = vec![b"http/1.1".to_vec()];
+    config.resumption = Resumption::store(Arc::new(TimingSessionStore::default()));
+    config
+}
+
+/// rustls's in-memory session cache. rustls asks it for a key share hint and a
+/// session to resume while building the ClientHello, which marks the handshake start.
+#[derive(Debug)]
+struct TimingSessionStore {
+    inner: ClientSessionMemoryCache,
+}
+
+impl Default for TimingSessionStore {
+    fn default() -> Self {
+        // The size rustls uses for its default store
+        Self {
+            inner: ClientSessionMemoryCache::new(256),
+        }
+    }
+}
+
+impl TimingSessionStore {
+    fn mark_handshake_start() {
+        if let Some(phases) = current_phases() {
+            let mut phases = phases.lock().unwrap_or_else(|err| err.into_inner());
+            phases.tls_start.get_or_insert_with(Instant::now);
+        }
+    }
+}
+
+impl ClientSessionStore for TimingSessionStore {
+    fn set_kx_hint(&self, server_name: ServerName<'static>, group: NamedGroup) {
+        self.inner.set_kx_hint(server_name, group);
+    }
+
+    fn kx_hint(&self, server_name: &ServerName<'_>) -> Option<NamedGroup> {
+        Self::mark_handshake_start();
+        self.inner.kx_hint(server_name)
+    }
+
+    fn set_tls12_session(&self, server_name: ServerName<'static>, value: Tls12ClientSessionValue) {
+        self.inner.set_tls12_session(server_name, value);
+    }
+
+    fn tls12_session(&self, server_name: &ServerName<'_>) -> Option<Tls12ClientSessionValue> {
+        Self::mark_handshake_start();
+        self.inner.tls12_session(server_name)
+    }
+
+    fn remove_tls12_session(&self, server_name: &ServerName<'static>) {
+        self.inner.remove_tls12_session(server_name);
+    }
+
+    fn insert_tls13_ticket(
+        &self,
+        server_name: ServerName<'static>,
+        value: Tls13ClientSessionValue,
+    ) {
+        self.inner.insert_tls13_ticket(server_name, value);
+    }
+
+    fn take_tls13_ticket(
+        &self,
+        server_name: &ServerName<'static>,
+    ) -> Option<Tls13ClientSessionValue> {
+        Self::mark_handshake_start();
+        self.inner.take_tls13_ticket(server_name)
+    }
+}
+
+/// Certificate verifier for `verify_ssl = false`, like reqwest's
+/// `danger_accept_invalid_certs`
+#[derive(Debug)]
+struct AcceptAnyCert;
+
+impl ServerCertVerifier for AcceptAnyCert {
+    fn verify_server_cert(
+        &self,
+        _end_entity: &CertificateDer<'_>,
+        _intermediates: &[CertificateDer<'_>],
+        _server_name: &ServerName<'_>,
+        _ocsp_response: &[u8],
+        _now: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        Ok(ServerCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        _message: &[u8],
+        _cert: &CertificateDer<'_>,
+        _dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        Ok(HandshakeSignatureValid::assertion())
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        _message: &[u8],
+        _cert: &CertificateDer<'_>,
+        _dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        Ok(HandshakeSignatureValid::assertion())
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        vec![
+            SignatureScheme::RSA_PKCS1_SHA1,
+            SignatureScheme::ECDSA_SHA1_Legacy,
+            SignatureScheme::RSA_PKCS1_SHA256,
+            SignatureScheme::ECDSA_NISTP256_SHA256,
+            SignatureScheme::RSA_PKCS1_SHA384,
+            SignatureScheme::ECDSA_NISTP384_SHA384,
+            SignatureScheme::RSA_PKCS1_SHA512,
+            SignatureScheme::ECDSA_NISTP521_SHA512,
+            SignatureScheme::RSA_PSS_SHA256,
+            SignatureScheme::RSA_PSS_SHA384,
+            SignatureScheme::RSA_PSS_SHA512,
+            SignatureScheme::ED25519,
+            SignatureScheme::ED448,
+        ]
+    }
+}
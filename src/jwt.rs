@@ -0,0 +1,112 @@
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::{DateTime, Utc};
+
+/// Decoded (but unverified) JWT header and payload
+#[derive(Debug, Clone)]
+pub struct DecodedJwt {
+    pub header: serde_json::Value,
+    pub payload: serde_json::Value,
+}
+
+impl DecodedJwt {
+    /// Get the `exp` claim as a UTC datetime, if present
+    pub fn expires_at(&self) -> Option<DateTime<Utc>> {
+        let exp = self.payload.get("exp")?.as_i64()?;
+        DateTime::from_timestamp(exp, 0)
+    }
+
+    /// Check whether the token is expired (false if there is no `exp` claim)
+    pub fn is_expired(&self) -> bool {
+        self.expires_at().is_some_and(|exp| exp <= Utc::now())
+    }
+
+    /// Header and payload as pretty JSON under `HEADER:` / `PAYLOAD:` separators
+    pub fn to_sections(&self) -> String {
+        let pretty = |value: &serde_json::Value| {
+            serde_json::to_string_pretty(value).unwrap_or_else(|_| value.to_string())
+        };
+        format!(
+            "HEADER:\n{}\n\nPAYLOAD:\n{}",
+            pretty(&self.header),
+            pretty(&self.payload)
+        )
+    }
+}
+
+/// Decode the header and payload sections of a JWT without verifying the signature
+pub fn decode_jwt(token: &str) -> Option<DecodedJwt> {
+    let token = token.trim();
+    let token = token.strip_prefix("Bearer ").unwrap_or(token);
+
+    let parts: Vec<&str> = token.split('.').collect();
+    if parts.len() != 3 {
+        return None;
+    }
+
+    let header = decode_part(parts[0])?;
+    let payload = decode_part(parts[1])?;
+
+    Some(DecodedJwt { header, payload })
+}
+
+fn decode_part(part: &str) -> Option<serde_json::Value> {
+    // Some encoders leave the padding in, URL_SAFE_NO_PAD rejects it
+    let bytes = URL_SAFE_NO_PAD.decode(part.trim_end_matches('=')).ok()?;
+    let value: serde_json::Value = serde_json::from_slice(&bytes).ok()?;
+    value.is_object().then_some(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encode(json: &str) -> String {
+        URL_SAFE_NO_PAD.encode(json.as_bytes())
+    }
+
+    #[test]
+    fn test_decode_valid_jwt() {
+        let token = format!(
+            "{}.{}.signature",
+            encode(r#"{"alg":"HS256","typ":"JWT"}"#),
+            encode(r#"{"sub":"1234","iss":"restui","exp":1}"#)
+        );
+        let decoded = decode_jwt(&token).unwrap();
+        assert_eq!(decoded.header["alg"], "HS256");
+        assert_eq!(decoded.payload["sub"], "1234");
+        assert!(decoded.is_expired());
+    }
+
+    #[test]
+    fn test_decode_without_exp_is_not_expired() {
+        let token = format!(
+            "{}.{}.sig",
+            encode(r#"{"alg":"none"}"#),
+            encode(r#"{"sub":"abc"}"#)
+        );
+        let decoded = decode_jwt(&token).unwrap();
+        assert!(decoded.expires_at().is_none());
+        assert!(!decoded.is_expired());
+    }
+
+    #[test]
+    fn test_to_sections() {
+        let token = format!(
+            "{}.{}.sig",
+            encode(r#"{"alg":"none"}"#),
+            encode(r#"{"sub":"abc"}"#)
+        );
+        let sections = decode_jwt(&token).unwrap().to_sections();
+        assert_eq!(
+            sections,
+            "HEADER:\n{\n  \"alg\": \"none\"\n}\n\nPAYLOAD:\n{\n  \"sub\": \"abc\"\n}"
+        );
+    }
+
+    #[test]
+    fn test_decode_invalid_jwt() {
+        assert!(decode_jwt("not-a-jwt").is_none());
+        assert!(decode_jwt("a.b.c").is_none());
+        assert!(decode_jwt("").is_none());
+    }
+}
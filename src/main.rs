@@ -2,28 +2,47 @@
 
 mod app;
 mod config;
+mod diff;
 mod filter;
+mod fuzzy;
+mod graphql;
 mod http;
+mod jwt;
+mod oauth2;
+mod schema;
+mod stats;
 mod storage;
 mod ui;
+mod update;
 
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use app::App;
+use config::Config;
 use crossterm::{
     event::{
         self, DisableFocusChange, DisableMouseCapture, EnableFocusChange, EnableMouseCapture,
-        Event, KeyEventKind, MouseButton, MouseEventKind,
+        Event, KeyEventKind, KeyboardEnhancementFlags, MouseButton, MouseEventKind,
+        PopKeyboardEnhancementFlags, PushKeyboardEnhancementFlags,
     },
     execute,
-    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+    terminal::{
+        disable_raw_mode, enable_raw_mode, supports_keyboard_enhancement, EnterAlternateScreen,
+        LeaveAlternateScreen,
+    },
 };
 use ratatui::{backend::CrosstermBackend, Terminal};
+use std::fs::{File, OpenOptions};
 use std::io::{self, Write};
 use std::panic;
+use std::path::{Path, PathBuf};
+use std::process::Command;
 use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
 use std::time::Duration;
+use tracing_subscriber::prelude::*;
 
 static TERMINAL_INITIALIZED: AtomicBool = AtomicBool::new(false);
+static KEYBOARD_ENHANCED: AtomicBool = AtomicBool::new(false);
 
 fn print_help() {
     println!("restui - A TUI API testing tool like Postman");
@@ -32,8 +51,12 @@ fn print_help() {
     println!("    restui [OPTIONS]");
     println!();
     println!("OPTIONS:");
-    println!("    -h, --help       Print help information");
-    println!("    -V, --version    Print version information");
+    println!("    --config-dir <PATH>    Use PATH for collections, history and settings");
+    println!("                           (remembered for later runs)");
+    println!("    --list-configs         List collection files and exit");
+    println!("    --check-update         Check GitHub for a newer release and exit");
+    println!("    -h, --help             Print help information");
+    println!("    -V, --version          Print version information");
 }
 
 fn print_version() {
@@ -43,9 +66,12 @@ fn print_version() {
 #[tokio::main]
 async fn main() -> Result<()> {
     // Handle command line arguments
-    let args: Vec<String> = std::env::args().collect();
-    if args.len() > 1 {
-        match args[1].as_str() {
+    let mut config_dir: Option<PathBuf> = None;
+    let mut list_configs = false;
+    let mut check_update = false;
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
             "-h" | "--help" => {
                 print_help();
                 return Ok(());
@@ -54,20 +80,86 @@ async fn main() -> Result<()> {
                 print_version();
                 return Ok(());
             }
-            arg => {
-                eprintln!("Unknown argument: {}", arg);
-                eprintln!("Use --help for usage information");
+            "--config-dir" => match args.next() {
+                Some(path) => config_dir = Some(PathBuf::from(path)),
+                None => {
+                    eprintln!("--config-dir requires a path");
+                    std::process::exit(1);
+                }
+            },
+            "--list-configs" => list_configs = true,
+            "--check-update" => check_update = true,
+            arg => match arg.strip_prefix("--config-dir=") {
+                Some(path) => config_dir = Some(PathBuf::from(path)),
+                None => {
+                    eprintln!("Unknown argument: {}", arg);
+                    eprintln!("Use --help for usage information");
+                    std::process::exit(1);
+                }
+            },
+        }
+    }
+
+    if check_update {
+        let current = update::current_version();
+        let latest = match update::fetch_latest_version(Duration::from_secs(10)).await {
+            Ok(latest) => latest,
+            Err(err) => {
+                eprintln!("Failed to check for updates: {}", err);
                 std::process::exit(1);
             }
+        };
+        if let Ok(config) = Config::new(config_dir) {
+            let _ = update::save_cache(&config.update_check_file, &latest);
+        }
+        if update::is_newer(&latest, current) {
+            println!("restui {} is available (you have {})", latest, current);
+        } else {
+            println!("restui {} is up to date", current);
+        }
+        return Ok(());
+    }
+
+    let remember = config_dir.is_some();
+    let config = Config::new(config_dir)?;
+    if remember {
+        config
+            .remember_dir()
+            .map_err(|e| anyhow!("Failed to remember config dir: {}", e))?;
+    }
+
+    if list_configs {
+        for path in config.collection_files()? {
+            println!("{}", path.display());
         }
+        return Ok(());
     }
-    // Set up logging (optional, for debugging)
-    tracing_subscriber::fmt()
-        .with_env_filter(
-            tracing_subscriber::EnvFilter::from_default_env()
-                .add_directive(tracing::Level::INFO.into()),
+
+    // Set up logging (optional, for debugging); with `log_to_file` on, restui's own
+    // events are also written to the log file at DEBUG level
+    let settings = storage::Settings::load(&config.settings_file).unwrap_or_default();
+    let log_file = settings
+        .log_to_file
+        .then(|| open_log_file(&settings.log_file(&config.log_file)))
+        .transpose()?;
+    tracing_subscriber::registry()
+        .with(
+            tracing_subscriber::fmt::layer()
+                .with_writer(std::io::stderr)
+                .with_filter(
+                    tracing_subscriber::EnvFilter::from_default_env()
+                        .add_directive(tracing::Level::INFO.into()),
+                ),
         )
-        .with_writer(std::io::stderr)
+        .with(log_file.map(|file| {
+            tracing_subscriber::fmt::layer()
+                .with_writer(Mutex::new(file))
+                .with_ansi(false)
+                .with_filter(
+                    tracing_subscriber::filter::Targets::new()
+                        .with_target(env!("CARGO_CRATE_NAME"), tracing::Level::DEBUG),
+                )
+        }))
         .init();
 
     // Set up panic hook to restore terminal on panic
@@ -90,12 +182,14 @@ async fn main() -> Result<()> {
         EnableMouseCapture,
         EnableFocusChange
     )?;
+    enable_keyboard_enhancement();
     TERMINAL_INITIALIZED.store(true, Ordering::SeqCst);
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
     // Create app and run
-    let mut app = App::new().await?;
+    let mut app = App::new(config).await?;
+    app.start_update_check();
     let result = run_app(&mut terminal, &mut app).await;
 
     // Restore terminal (also show cursor which restore_terminal doesn't do)
@@ -151,6 +245,11 @@ async fn run_app(
                                             app.set_error(format!("Error: {e}"));
                                         }
                                     }
+                                    if let Some(path) = app.pending_pager.take() {
+                                        if let Err(e) = open_in_pager(terminal, &path) {
+                                            app.set_error(format!("Error: {e}"));
+                                        }
+                                    }
                                 }
                             }
                             Event::Mouse(mouse) => match mouse.kind {
@@ -160,6 +259,12 @@ async fn run_app(
                                 MouseEventKind::Drag(MouseButton::Left) => {
                                     app.handle_mouse_drag(mouse.column, mouse.row);
                                 }
+                                MouseEventKind::Up(MouseButton::Left) => {
+                                    app.handle_mouse_up();
+                                }
+                                MouseEventKind::Moved => {
+                                    app.handle_mouse_move(mouse.column, mouse.row);
+                                }
                                 MouseEventKind::ScrollUp => {
                                     app.handle_scroll(mouse.column, mouse.row, true);
                                 }
@@ -208,9 +313,68 @@ async fn run_app(
     }
 }
 
+/// Open the debug log for appending, creating it and its directory if needed
+fn open_log_file(path: &Path) -> Result<File> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|e| anyhow!("Failed to open log file {}: {}", path.display(), e))
+}
+
+/// Show `path` in `$PAGER` (default `less`), handing the terminal over until it exits
+fn open_in_pager(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>, path: &Path) -> Result<()> {
+    let pager = std::env::var("PAGER")
+        .ok()
+        .filter(|pager| !pager.trim().is_empty())
+        .unwrap_or_else(|| "less".to_string());
+    let mut parts = pager.split_whitespace();
+    let program = parts.next().unwrap_or("less");
+
+    restore_terminal();
+    let status = Command::new(program).args(parts).arg(path).status();
+    enable_raw_mode()?;
+    execute!(
+        terminal.backend_mut(),
+        EnterAlternateScreen,
+        EnableMouseCapture,
+        EnableFocusChange
+    )?;
+    enable_keyboard_enhancement();
+    terminal.clear()?;
+
+    let status = status.map_err(|e| anyhow!("Failed to run {}: {}", program, e))?;
+    if !status.success() {
+        return Err(anyhow!("{} exited with {}", program, status));
+    }
+    Ok(())
+}
+
+/// Ask the terminal to report modified keys unambiguously (kitty keyboard
+/// protocol), so chords like Ctrl+Shift+C or Ctrl+Enter reach the app instead of
+/// arriving as Ctrl+C or Enter. Terminals without support keep the legacy
+/// encoding, which is why those chords all have a fallback key.
+fn enable_keyboard_enhancement() {
+    if matches!(supports_keyboard_enhancement(), Ok(true))
+        && execute!(
+            io::stdout(),
+            PushKeyboardEnhancementFlags(KeyboardEnhancementFlags::DISAMBIGUATE_ESCAPE_CODES)
+        )
+        .is_ok()
+    {
+        KEYBOARD_ENHANCED.store(true, Ordering::SeqCst);
+    }
+}
+
 /// Restore terminal to normal state
 /// This is called on panic and normal exit to ensure terminal is usable
 fn restore_terminal() {
+    if KEYBOARD_ENHANCED.swap(false, Ordering::SeqCst) {
+        let _ = execute!(io::stdout(), PopKeyboardEnhancementFlags);
+    }
     let _ = disable_raw_mode();
     let _ = execute!(
         io::stdout(),
@@ -0,0 +1,231 @@
+use crate::storage::OAuth2Config;
+use anyhow::{anyhow, bail, Result};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use reqwest::Url;
+use sha2::{Digest, Sha256};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+/// Where the browser is sent back to when the request sets no redirect URI
+pub const DEFAULT_REDIRECT_URI: &str = "http://localhost:9999/callback";
+
+/// How long to wait for the user to finish signing in
+const CALLBACK_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Token endpoint timeout
+const TOKEN_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Page shown in the browser once the code has been received
+const CALLBACK_PAGE: &str = "<html><body><h3>Authorization received.</h3>\
+    <p>You can close this window and return to restui.</p></body></html>";
+
+/// Random PKCE code verifier: 64 base64url characters from 48 random bytes
+pub fn generate_code_verifier() -> String {
+    let bytes: Vec<u8> = (0..3)
+        .flat_map(|_| uuid::Uuid::new_v4().into_bytes())
+        .collect();
+    URL_SAFE_NO_PAD.encode(bytes)
+}
+
+/// S256 code challenge for `verifier`
+pub fn code_challenge(verifier: &str) -> String {
+    URL_SAFE_NO_PAD.encode(Sha256::digest(verifier.as_bytes()))
+}
+
+pub fn redirect_uri(config: &OAuth2Config) -> &str {
+    if config.redirect_uri.trim().is_empty() {
+        DEFAULT_REDIRECT_URI
+    } else {
+        config.redirect_uri.trim()
+    }
+}
+
+/// Authorization endpoint URL the browser is sent to
+pub fn authorization_url(config: &OAuth2Config, challenge: &str, state: &str) -> Result<Url> {
+    let mut url =
+        Url::parse(config.auth_url.trim()).map_err(|err| anyhow!("Invalid auth URL: {}", err))?;
+    {
+        let mut query = url.query_pairs_mut();
+        query
+            .append_pair("response_type", "code")
+            .append_pair("client_id", &config.client_id)
+            .append_pair("redirect_uri", redirect_uri(config));
+        if !config.scope.trim().is_empty() {
+            query.append_pair("scope", config.scope.trim());
+        }
+        query
+            .append_pair("state", state)
+            .append_pair("code_challenge", challenge)
+            .append_pair("code_challenge_method", "S256");
+    }
+    Ok(url)
+}
+
+/// Authorization code from the request line of the redirect, `None` for
+/// unrelated requests such as the browser asking for a favicon
+pub fn parse_callback(request_line: &str, state: &str) -> Result<Option<String>> {
+    let target = request_line.split_whitespace().nth(1).unwrap_or("/");
+    let url = Url::parse("http://localhost")?.join(target)?;
+    let param = |name: &str| {
+        url.query_pairs()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.into_owned())
+    };
+    if let Some(error) = param("error") {
+        match param("error_description") {
+            Some(description) => bail!("Authorization failed: {} ({})", error, description),
+            None => bail!("Authorization failed: {}", error),
+        }
+    }
+    let Some(code) = param("code") else {
+        return Ok(None);
+    };
+    if param("state").as_deref() != Some(state) {
+        bail!("Authorization response has the wrong state");
+    }
+    Ok(Some(code))
+}
+
+/// `access_token` from a token endpoint response
+pub fn parse_token_response(body: &str) -> Result<String> {
+    let json: serde_json::Value =
+        serde_json::from_str(body).map_err(|_| anyhow!("Token response is not JSON"))?;
+    if let Some(token) = json.get("access_token").and_then(|token| token.as_str()) {
+        return Ok(token.to_string());
+    }
+    let error = json
+        .get("error_description")
+        .or_else(|| json.get("error"))
+        .and_then(|error| error.as_str())
+        .unwrap_or("no access_token in response");
+    bail!("Token request failed: {}", error)
+}
+
+/// Run the whole flow: listen on the redirect address, open the authorization
+/// page in the browser, wait for the code and exchange it for an access token
+pub async fn authorize(config: OAuth2Config) -> Result<String> {
+    let verifier = generate_code_verifier();
+    let state = uuid::Uuid::new_v4().simple().to_string();
+    let url = authorization_url(&config, &code_challenge(&verifier), &state)?;
+
+    let redirect = Url::parse(redirect_uri(&config))
+        .map_err(|err| anyhow!("Invalid redirect URI: {}", err))?;
+    let host = redirect.host_str().unwrap_or("localhost").to_string();
+    let port = redirect.port_or_known_default().unwrap_or(80);
+    let listener = TcpListener::bind((host.as_str(), port))
+        .await
+        .map_err(|err| anyhow!("Cannot listen on {}:{}: {}", host, port, err))?;
+
+    open::that(url.as_str()).map_err(|err| anyhow!("Cannot open the browser: {}", err))?;
+
+    let code = tokio::time::timeout(CALLBACK_TIMEOUT, wait_for_code(&listener, &state))
+        .await
+        .map_err(|_| anyhow!("Timed out waiting for browser authorization"))??;
+    exchange_code(&config, &code, &verifier).await
+}
+
+async fn wait_for_code(listener: &TcpListener, state: &str) -> Result<String> {
+    loop {
+        let (mut stream, _) = listener.accept().await?;
+        let mut buffer = vec![0u8; 8192];
+        let read = stream.read(&mut buffer).await?;
+        let request = String::from_utf8_lossy(&buffer[..read]);
+        let request_line = request.lines().next().unwrap_or_default();
+        let result = parse_callback(request_line, state);
+        let (status, page) = match &result {
+            Ok(Some(_)) => ("200 OK", CALLBACK_PAGE.to_string()),
+            Ok(None) => ("404 Not Found", String::new()),
+            Err(err) => (
+                "400 Bad Request",
+                format!("<html><body>{}</body></html>", err),
+            ),
+        };
+        let response = format!(
+            "HTTP/1.1 {}\r\nContent-Type: text/html; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            status,
+            page.len(),
+            page
+        );
+        let _ = stream.write_all(response.as_bytes()).await;
+        if let Some(code) = result? {
+            return Ok(code);
+        }
+    }
+}
+
+async fn exchange_code(config: &OAuth2Config, code: &str, verifier: &str) -> Result<String> {
+    let client = reqwest::Client::builder().timeout(TOKEN_TIMEOUT).build()?;
+    let response = client
+        .post(config.token_url.trim())
+        .header("Accept", "application/json")
+        .form(&[
+            ("grant_type", "authorization_code"),
+            ("code", code),
+            ("redirect_uri", redirect_uri(config)),
+            ("client_id", config.client_id.as_str()),
+            ("code_verifier", verifier),
+        ])
+        .send()
+        .await?;
+    parse_token_response(&response.text().await?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_code_challenge() {
+        // base64url(SHA-256(verifier)) without padding
+        assert_eq!(
+            code_challenge("0123456789abcdefghijklmnopqrstuvwxyzABCDEFG"),
+            "g0tuZ6q412zO9IRkeAUs8HN6MQeXPsGce37J3Rsc8wQ"
+        );
+        let verifier = generate_code_verifier();
+        assert_eq!(verifier.len(), 64);
+        assert!(verifier
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'));
+        assert_ne!(verifier, generate_code_verifier());
+    }
+
+    #[test]
+    fn test_authorization_url() {
+        let config = OAuth2Config {
+            auth_url: "https://auth.example.com/authorize".to_string(),
+            client_id: "app".to_string(),
+            scope: "read write".to_string(),
+            ..Default::default()
+        };
+        let url = authorization_url(&config, "challenge", "xyz").unwrap();
+        let query: Vec<(String, String)> = url.query_pairs().into_owned().collect();
+        assert!(query.contains(&("redirect_uri".into(), DEFAULT_REDIRECT_URI.into())));
+        assert!(query.contains(&("scope".into(), "read write".into())));
+        assert!(query.contains(&("code_challenge_method".into(), "S256".into())));
+        assert!(authorization_url(&OAuth2Config::default(), "c", "s").is_err());
+    }
+
+    #[test]
+    fn test_parse_callback() {
+        assert_eq!(
+            parse_callback("GET /callback?code=abc&state=xyz HTTP/1.1", "xyz").unwrap(),
+            Some("abc".to_string())
+        );
+        assert_eq!(
+            parse_callback("GET /favicon.ico HTTP/1.1", "xyz").unwrap(),
+            None
+        );
+        assert!(parse_callback("GET /callback?code=abc&state=evil HTTP/1.1", "xyz").is_err());
+        assert!(parse_callback("GET /callback?error=access_denied HTTP/1.1", "xyz").is_err());
+
+        assert_eq!(
+            parse_token_response(r#"{"access_token": "t0k", "token_type": "Bearer"}"#).unwrap(),
+            "t0k"
+        );
+        assert!(parse_token_response(r#"{"error": "invalid_grant"}"#)
+            .unwrap_err()
+            .to_string()
+            .contains("invalid_grant"));
+    }
+}
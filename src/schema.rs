@@ -0,0 +1,105 @@
+use serde_json::Value;
+
+/// One way a response body fails its JSON Schema
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaValidationError {
+    /// JSON pointer to the offending value, empty for the document root
+    pub path: String,
+    pub message: String,
+}
+
+impl SchemaValidationError {
+    fn new(path: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            message: message.into(),
+        }
+    }
+}
+
+/// Validate `body` against `schema`; an empty result means the body is valid.
+/// An unparsable schema or a non-JSON body is reported as a single error.
+pub fn validate(schema: &str, body: &str) -> Vec<SchemaValidationError> {
+    let schema: Value = match serde_json::from_str(schema) {
+        Ok(schema) => schema,
+        Err(err) => {
+            return vec![SchemaValidationError::new(
+                "",
+                format!("Schema is not JSON: {}", err),
+            )]
+        }
+    };
+    let validator = match jsonschema::validator_for(&schema) {
+        Ok(validator) => validator,
+        Err(err) => {
+            return vec![SchemaValidationError::new(
+                "",
+                format!("Invalid schema: {}", err),
+            )]
+        }
+    };
+    let instance: Value = match serde_json::from_str(body) {
+        Ok(instance) => instance,
+        Err(err) => {
+            return vec![SchemaValidationError::new(
+                "",
+                format!("Response is not JSON: {}", err),
+            )]
+        }
+    };
+
+    validator
+        .iter_errors(&instance)
+        .map(|err| SchemaValidationError::new(err.instance_path.to_string(), err.to_string()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCHEMA: &str = r#"{
+        "type": "object",
+        "required": ["id", "name"],
+        "properties": {
+            "id": { "type": "integer" },
+            "name": { "type": "string" },
+            "tags": { "type": "array", "items": { "type": "string" } }
+        }
+    }"#;
+
+    #[test]
+    fn test_validate_valid_body() {
+        let body = r#"{"id": 1, "name": "Ada", "tags": ["admin"]}"#;
+        assert!(validate(SCHEMA, body).is_empty());
+    }
+
+    #[test]
+    fn test_validate_reports_pointer_paths() {
+        let body = r#"{"id": "1", "name": "Ada", "tags": ["admin", 2]}"#;
+        let mut paths: Vec<String> = validate(SCHEMA, body)
+            .into_iter()
+            .map(|err| err.path)
+            .collect();
+        paths.sort();
+        assert_eq!(paths, vec!["/id", "/tags/1"]);
+
+        let errors = validate(SCHEMA, r#"{"id": 1}"#);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].path, "");
+        assert!(errors[0].message.contains("name"));
+    }
+
+    #[test]
+    fn test_validate_bad_input() {
+        assert!(validate("{", "{}")[0]
+            .message
+            .starts_with("Schema is not JSON"));
+        assert!(validate(r#"{"type": 5}"#, "{}")[0]
+            .message
+            .starts_with("Invalid schema"));
+        assert!(validate(SCHEMA, "<html>")[0]
+            .message
+            .starts_with("Response is not JSON"));
+    }
+}
@@ -0,0 +1,151 @@
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+/// How many keys or words the summary lists
+pub const TOP_COUNT: usize = 10;
+
+/// Shape of a JSON document: its most used keys and how many values of each type
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct JsonStats {
+    /// Most frequent object keys across the whole document, most frequent first
+    pub top_keys: Vec<(String, usize)>,
+    pub objects: usize,
+    pub arrays: usize,
+    pub strings: usize,
+    pub numbers: usize,
+    pub booleans: usize,
+    pub nulls: usize,
+}
+
+impl JsonStats {
+    pub fn to_json(&self) -> Value {
+        json!({
+            "top_keys": counts_to_json(&self.top_keys),
+            "objects": self.objects,
+            "arrays": self.arrays,
+            "strings": self.strings,
+            "numbers": self.numbers,
+            "booleans": self.booleans,
+            "nulls": self.nulls,
+        })
+    }
+}
+
+/// Summary of a response body: key and type counts for JSON, word counts otherwise
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResponseStats {
+    Json(JsonStats),
+    Words(Vec<(String, usize)>),
+}
+
+impl ResponseStats {
+    pub fn to_json(&self) -> Value {
+        match self {
+            ResponseStats::Json(stats) => stats.to_json(),
+            ResponseStats::Words(words) => json!({ "top_words": counts_to_json(words) }),
+        }
+    }
+}
+
+/// Walk `value` counting keys (at any depth) and values by type
+pub fn analyze_json(value: &Value) -> JsonStats {
+    let mut stats = JsonStats::default();
+    let mut keys: HashMap<&str, usize> = HashMap::new();
+    let mut stack = vec![value];
+    while let Some(value) = stack.pop() {
+        match value {
+            Value::Object(map) => {
+                stats.objects += 1;
+                for (key, child) in map {
+                    *keys.entry(key.as_str()).or_default() += 1;
+                    stack.push(child);
+                }
+            }
+            Value::Array(items) => {
+                stats.arrays += 1;
+                stack.extend(items);
+            }
+            Value::String(_) => stats.strings += 1,
+            Value::Number(_) => stats.numbers += 1,
+            Value::Bool(_) => stats.booleans += 1,
+            Value::Null => stats.nulls += 1,
+        }
+    }
+    stats.top_keys = top_counts(keys);
+    stats
+}
+
+/// Most frequent whitespace-separated words, most frequent first
+pub fn word_frequency(text: &str) -> Vec<(String, usize)> {
+    let mut words: HashMap<&str, usize> = HashMap::new();
+    for word in text.split_whitespace() {
+        *words.entry(word).or_default() += 1;
+    }
+    top_counts(words)
+}
+
+/// The TOP_COUNT largest counts; ties are broken alphabetically so the order is stable
+fn top_counts(counts: HashMap<&str, usize>) -> Vec<(String, usize)> {
+    let mut counts: Vec<(String, usize)> = counts
+        .into_iter()
+        .map(|(key, count)| (key.to_string(), count))
+        .collect();
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    counts.truncate(TOP_COUNT);
+    counts
+}
+
+/// `[key, count]` pairs; an object would lose the most-frequent-first order
+fn counts_to_json(counts: &[(String, usize)]) -> Value {
+    counts
+        .iter()
+        .map(|(key, count)| json!([key, count]))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_analyze_json() {
+        let value: Value = serde_json::from_str(
+            r#"{"users": [{"id": 1, "name": "Ada", "admin": true}, {"id": 2, "name": null}]}"#,
+        )
+        .unwrap();
+        let stats = analyze_json(&value);
+        assert_eq!(stats.objects, 3);
+        assert_eq!(stats.arrays, 1);
+        assert_eq!(stats.strings, 1);
+        assert_eq!(stats.numbers, 2);
+        assert_eq!(stats.booleans, 1);
+        assert_eq!(stats.nulls, 1);
+        assert_eq!(
+            stats.top_keys,
+            vec![
+                ("id".to_string(), 2),
+                ("name".to_string(), 2),
+                ("admin".to_string(), 1),
+                ("users".to_string(), 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_word_frequency() {
+        let words = word_frequency("b a b\nc b a");
+        assert_eq!(
+            words,
+            vec![
+                ("b".to_string(), 3),
+                ("a".to_string(), 2),
+                ("c".to_string(), 1),
+            ]
+        );
+        let stats = ResponseStats::Words(words);
+        assert_eq!(
+            stats.to_json(),
+            json!({"top_words": [["b", 3], ["a", 2], ["c", 1]]})
+        );
+    }
+}
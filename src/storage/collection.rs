@@ -1,6 +1,7 @@
-use super::request::ApiRequest;
+use super::request::{ApiRequest, AuthConfig};
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::Path;
 use uuid::Uuid;
 
@@ -53,12 +54,34 @@ impl CollectionItem {
     }
 }
 
+/// Outcome of [`Collection::merge`]
+#[derive(Debug, Clone)]
+pub struct MergeResult {
+    pub merged: Collection,
+    /// Names of requests copied over from the other collection
+    pub added: Vec<String>,
+    /// Same-named requests with different contents: (existing, imported copy)
+    pub conflicts: Vec<(ApiRequest, ApiRequest)>,
+}
+
 /// A collection of API requests
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Collection {
     pub id: String,
     pub name: String,
     pub items: Vec<CollectionItem>,
+    /// Auth inherited by requests that have `use_collection_auth` set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_auth: Option<AuthConfig>,
+    /// Label color for the collection header (hex like `#ff6b6b` or a color name)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    /// Notes about the collection (plain text or Markdown), shown before a request is opened
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub readme: Option<String>,
+    /// Variables for this collection's requests, used when no environment defines the key
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub variables: HashMap<String, String>,
     #[serde(skip)]
     pub expanded: bool,
     /// Path this collection was loaded from (for deletion)
@@ -72,6 +95,10 @@ impl Collection {
             id: Uuid::new_v4().to_string(),
             name: name.into(),
             items: Vec::new(),
+            default_auth: None,
+            color: None,
+            readme: None,
+            variables: HashMap::new(),
             expanded: true,
             source_path: None,
         }
@@ -157,51 +184,171 @@ impl Collection {
         }
     }
 
-    /// Find a request by ID
-    pub fn find_request(&self, id: &str) -> Option<&ApiRequest> {
-        Self::find_request_in_items(&self.items, id)
+    /// All requests in the collection, including those in collapsed folders
+    pub fn find_all_requests(&self) -> Vec<&ApiRequest> {
+        let mut result = Vec::new();
+        Self::collect_requests(&self.items, &mut result);
+        result
     }
 
-    fn find_request_in_items<'a>(items: &'a [CollectionItem], id: &str) -> Option<&'a ApiRequest> {
+    fn collect_requests<'a>(items: &'a [CollectionItem], result: &mut Vec<&'a ApiRequest>) {
         for item in items {
             match item {
-                CollectionItem::Request(req) if req.id == id => return Some(req),
-                CollectionItem::Folder { items, .. } => {
-                    if let Some(req) = Self::find_request_in_items(items, id) {
-                        return Some(req);
-                    }
-                }
-                _ => {}
+                CollectionItem::Request(req) => result.push(req),
+                CollectionItem::Folder { items, .. } => Self::collect_requests(items, result),
             }
         }
-        None
     }
 
-    /// Find and update a request by ID
-    pub fn update_request(&mut self, id: &str, mut f: impl FnMut(&mut ApiRequest)) -> bool {
-        Self::update_request_in_items(&mut self.items, id, &mut f)
+    /// Mutable access to every request, including those in collapsed folders
+    pub fn find_all_requests_mut(&mut self) -> Vec<&mut ApiRequest> {
+        let mut result = Vec::new();
+        Self::collect_requests_mut(&mut self.items, &mut result);
+        result
     }
 
-    fn update_request_in_items(
-        items: &mut [CollectionItem],
-        id: &str,
-        f: &mut impl FnMut(&mut ApiRequest),
-    ) -> bool {
+    fn collect_requests_mut<'a>(
+        items: &'a mut [CollectionItem],
+        result: &mut Vec<&'a mut ApiRequest>,
+    ) {
         for item in items {
             match item {
-                CollectionItem::Request(req) if req.id == id => {
-                    f(req);
-                    return true;
+                CollectionItem::Request(req) => result.push(req),
+                CollectionItem::Folder { items, .. } => Self::collect_requests_mut(items, result),
+            }
+        }
+    }
+
+    pub fn count_requests(&self) -> usize {
+        self.find_all_requests().len()
+    }
+
+    /// Number of folders at any depth
+    pub fn count_folders(&self) -> usize {
+        fn count(items: &[CollectionItem]) -> usize {
+            items
+                .iter()
+                .map(|item| match item {
+                    CollectionItem::Folder { items, .. } => 1 + count(items),
+                    CollectionItem::Request(_) => 0,
+                })
+                .sum()
+        }
+        count(&self.items)
+    }
+
+    /// Merge another collection into a copy of this one. Folders with the same
+    /// name are merged recursively; identical requests are skipped, and
+    /// same-named requests that differ are kept as `"name (imported)"`.
+    pub fn merge(&self, other: &Collection) -> MergeResult {
+        let mut merged = self.clone();
+        let mut added = Vec::new();
+        let mut conflicts = Vec::new();
+        Self::merge_items(&mut merged.items, &other.items, &mut added, &mut conflicts);
+        MergeResult {
+            merged,
+            added,
+            conflicts,
+        }
+    }
+
+    fn merge_items(
+        target: &mut Vec<CollectionItem>,
+        incoming: &[CollectionItem],
+        added: &mut Vec<String>,
+        conflicts: &mut Vec<(ApiRequest, ApiRequest)>,
+    ) {
+        for item in incoming {
+            match item {
+                CollectionItem::Folder { name, items, .. } => {
+                    let existing = target.iter_mut().find_map(|existing| match existing {
+                        CollectionItem::Folder {
+                            name: existing_name,
+                            items: existing_items,
+                            ..
+                        } if existing_name == name => Some(existing_items),
+                        _ => None,
+                    });
+                    match existing {
+                        Some(existing_items) => {
+                            Self::merge_items(existing_items, items, added, conflicts)
+                        }
+                        None => {
+                            let mut folder = item.clone();
+                            Self::assign_new_ids(&mut folder, added);
+                            target.push(folder);
+                        }
+                    }
                 }
-                CollectionItem::Folder { items, .. } => {
-                    if Self::update_request_in_items(items, id, f) {
-                        return true;
+                CollectionItem::Request(request) => {
+                    let existing = target.iter().find_map(|existing| match existing {
+                        CollectionItem::Request(existing) if existing.name == request.name => {
+                            Some(existing)
+                        }
+                        _ => None,
+                    });
+                    let mut imported = request.clone();
+                    imported.id = Uuid::new_v4().to_string();
+                    match existing {
+                        Some(existing) if Self::same_contents(existing, request) => {}
+                        Some(existing) => {
+                            imported.name = format!("{} (imported)", request.name);
+                            conflicts.push((existing.clone(), imported.clone()));
+                            target.push(CollectionItem::Request(imported));
+                        }
+                        None => {
+                            added.push(imported.name.clone());
+                            target.push(CollectionItem::Request(imported));
+                        }
                     }
                 }
-                _ => {}
             }
         }
-        false
+    }
+
+    /// Give a copied item (and everything inside it) fresh IDs, recording request names
+    fn assign_new_ids(item: &mut CollectionItem, added: &mut Vec<String>) {
+        match item {
+            CollectionItem::Request(request) => {
+                request.id = Uuid::new_v4().to_string();
+                added.push(request.name.clone());
+            }
+            CollectionItem::Folder { id, items, .. } => {
+                *id = Uuid::new_v4().to_string();
+                for item in items {
+                    Self::assign_new_ids(item, added);
+                }
+            }
+        }
+    }
+
+    /// Compare two requests ignoring their IDs
+    fn same_contents(a: &ApiRequest, b: &ApiRequest) -> bool {
+        let mut b = b.clone();
+        b.id = a.id.clone();
+        serde_json::to_value(a).ok() == serde_json::to_value(&b).ok()
+    }
+
+    /// Find a request by ID
+    pub fn find_request(&self, id: &str) -> Option<&ApiRequest> {
+        self.find_all_requests()
+            .into_iter()
+            .find(|req| req.id == id)
+    }
+
+    /// Find and update a request by ID
+    pub fn update_request(&mut self, id: &str, f: impl FnOnce(&mut ApiRequest)) -> bool {
+        match self
+            .find_all_requests_mut()
+            .into_iter()
+            .find(|req| req.id == id)
+        {
+            Some(req) => {
+                f(req);
+                true
+            }
+            None => false,
+        }
     }
 
     /// Rename the collection
@@ -369,6 +516,65 @@ impl Collection {
         false
     }
 
+    /// Index of an item within its parent folder (or the collection root)
+    pub fn item_position(&self, item_id: &str) -> Option<usize> {
+        Self::item_position_recursive(&self.items, item_id)
+    }
+
+    fn item_position_recursive(items: &[CollectionItem], item_id: &str) -> Option<usize> {
+        if let Some(pos) = items.iter().position(|item| item.id() == item_id) {
+            return Some(pos);
+        }
+        items.iter().find_map(|item| match item {
+            CollectionItem::Folder {
+                items: folder_items,
+                ..
+            } => Self::item_position_recursive(folder_items, item_id),
+            CollectionItem::Request(_) => None,
+        })
+    }
+
+    /// Insert an item at `position` in a folder (or root if folder_id is None),
+    /// appending if the folder has fewer items
+    pub fn insert_item_at(
+        &mut self,
+        item: CollectionItem,
+        folder_id: Option<&str>,
+        position: usize,
+    ) -> bool {
+        let items = match folder_id {
+            None => &mut self.items,
+            Some(id) => match Self::folder_items_mut(&mut self.items, id) {
+                Some(items) => items,
+                None => return false,
+            },
+        };
+        items.insert(position.min(items.len()), item);
+        true
+    }
+
+    fn folder_items_mut<'a>(
+        items: &'a mut [CollectionItem],
+        folder_id: &str,
+    ) -> Option<&'a mut Vec<CollectionItem>> {
+        for item in items {
+            if let CollectionItem::Folder {
+                id,
+                items: folder_items,
+                ..
+            } = item
+            {
+                if id == folder_id {
+                    return Some(folder_items);
+                }
+                if let Some(found) = Self::folder_items_mut(folder_items, folder_id) {
+                    return Some(found);
+                }
+            }
+        }
+        None
+    }
+
     /// Rename an item by ID
     pub fn rename_item(&mut self, item_id: &str, new_name: impl Into<String>) -> bool {
         Self::rename_item_recursive(&mut self.items, item_id, new_name.into())
@@ -414,3 +620,143 @@ impl Default for Collection {
         Self::new("New Collection")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(name: &str, url: &str) -> CollectionItem {
+        let mut request = ApiRequest::new(name);
+        request.url = url.to_string();
+        CollectionItem::Request(request)
+    }
+
+    #[test]
+    fn test_merge_collections() {
+        let mut ours = Collection::new("API");
+        ours.items.push(request("List", "/users"));
+        ours.items.push(request("Get", "/users/1"));
+        let mut folder = CollectionItem::new_folder("Admin");
+        if let CollectionItem::Folder { items, .. } = &mut folder {
+            items.push(request("Stats", "/stats"));
+        }
+        ours.items.push(folder);
+
+        let mut theirs = Collection::new("API");
+        theirs.items.push(request("List", "/users"));
+        theirs.items.push(request("Get", "/users/{id}"));
+        theirs.items.push(request("Create", "/users"));
+        let mut folder = CollectionItem::new_folder("Admin");
+        if let CollectionItem::Folder { items, .. } = &mut folder {
+            items.push(request("Reset", "/reset"));
+        }
+        theirs.items.push(folder);
+
+        let result = ours.merge(&theirs);
+        assert_eq!(result.added, vec!["Create", "Reset"]);
+        assert_eq!(result.conflicts.len(), 1);
+        assert_eq!(result.conflicts[0].1.name, "Get (imported)");
+
+        let names: Vec<&str> = result
+            .merged
+            .find_all_requests()
+            .iter()
+            .map(|r| r.name.as_str())
+            .collect();
+        assert_eq!(
+            names,
+            vec!["List", "Get", "Stats", "Reset", "Get (imported)", "Create"]
+        );
+    }
+
+    #[test]
+    fn test_reinsert_item_at_position() {
+        let mut collection = Collection::new("API");
+        collection.items.push(request("List", "/users"));
+        let mut folder = CollectionItem::new_folder("Admin");
+        let folder_id = folder.id().to_string();
+        if let CollectionItem::Folder { items, .. } = &mut folder {
+            items.push(request("Stats", "/stats"));
+            items.push(request("Reset", "/reset"));
+        }
+        collection.items.push(folder);
+
+        let stats_id = collection.find_all_requests()[1].id.clone();
+        assert_eq!(collection.item_position(&stats_id), Some(0));
+
+        let item = collection.extract_item(&stats_id).unwrap();
+        assert!(collection.insert_item_at(item, Some(&folder_id), 0));
+        let names: Vec<&str> = collection
+            .find_all_requests()
+            .iter()
+            .map(|r| r.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["List", "Stats", "Reset"]);
+
+        assert!(collection.insert_item_at(request("Create", "/users"), None, 99));
+        assert_eq!(collection.items.last().unwrap().name(), "Create");
+        assert!(!collection.insert_item_at(request("Lost", "/"), Some("missing"), 0));
+    }
+
+    #[test]
+    fn test_find_and_count_requests() {
+        let mut collection = Collection::new("API");
+        collection.items.push(request("List", "/users"));
+        let mut folder = CollectionItem::new_folder("Admin");
+        if let CollectionItem::Folder {
+            items, expanded, ..
+        } = &mut folder
+        {
+            *expanded = false;
+            items.push(request("Stats", "/stats"));
+            items.push(CollectionItem::new_folder("Empty"));
+        }
+        collection.items.push(folder);
+
+        assert_eq!(collection.count_requests(), 2);
+        assert_eq!(collection.count_folders(), 2);
+
+        for req in collection.find_all_requests_mut() {
+            req.url = format!("https://api.test{}", req.url);
+        }
+        let urls: Vec<&str> = collection
+            .find_all_requests()
+            .iter()
+            .map(|r| r.url.as_str())
+            .collect();
+        assert_eq!(
+            urls,
+            vec!["https://api.test/users", "https://api.test/stats"]
+        );
+
+        let stats_id = collection.find_all_requests()[1].id.clone();
+        assert!(collection.update_request(&stats_id, |r| r.name = "Metrics".to_string()));
+        assert_eq!(collection.find_request(&stats_id).unwrap().name, "Metrics");
+        assert!(!collection.update_request("missing", |_| {}));
+    }
+
+    #[test]
+    fn test_collection_variables_are_the_last_fallback() {
+        let mut collection = Collection::new("API");
+        collection
+            .variables
+            .insert("base_url".into(), "http://ignored".into());
+        collection
+            .variables
+            .insert("BASE_PATH".into(), "/v2".into());
+
+        let json = serde_json::to_string(&collection).unwrap();
+        let loaded: Collection = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.variables, collection.variables);
+        let legacy: Collection =
+            serde_json::from_str(r#"{"id":"1","name":"Old","items":[]}"#).unwrap();
+        assert!(legacy.variables.is_empty());
+
+        let environments = crate::storage::EnvironmentManager::new();
+        assert_eq!(
+            environments
+                .interpolate_with_collection("{{base_url}}{{BASE_PATH}}/{{id}}", &loaded.variables),
+            "http://localhost:3000/v2/{{id}}"
+        );
+    }
+}
@@ -1,8 +1,8 @@
 use anyhow::Result;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use std::path::Path;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
 
 fn interpolate_with_vars(vars: &HashMap<String, String>, input: &str) -> String {
     let re = Regex::new(r"\{\{(\w+)\}\}").unwrap();
@@ -23,6 +23,9 @@ pub struct Environment {
     /// Optional color for the header bar (e.g., "red", "green", "blue", "yellow", "magenta", "cyan")
     #[serde(default)]
     pub color: Option<String>,
+    /// Keys whose values are masked in the UI (still stored in plaintext)
+    #[serde(default)]
+    pub secret_keys: Vec<String>,
 }
 
 impl Environment {
@@ -31,6 +34,7 @@ impl Environment {
             name: name.into(),
             variables: HashMap::new(),
             color: None,
+            secret_keys: Vec::new(),
         }
     }
 
@@ -56,21 +60,76 @@ impl Default for Environment {
     }
 }
 
+/// Variables merged in from an additional environment file (never saved back)
+#[derive(Debug, Clone)]
+pub struct AdditionalEnvFile {
+    pub path: PathBuf,
+    pub shared: HashMap<String, String>,
+    pub shared_secret_keys: Vec<String>,
+    pub environments: Vec<Environment>,
+}
+
+impl AdditionalEnvFile {
+    /// File name shown as the source of a merged variable
+    pub fn label(&self) -> String {
+        self.path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| self.path.display().to_string())
+    }
+}
+
+/// Scope name used for shared variables in [`EnvDiff`] keys
+pub const SHARED_SCOPE: &str = "(shared)";
+
+/// Variable changes between two sets of environments. Keys are qualified by
+/// scope as `"(shared): KEY"` or `"ENVIRONMENT: KEY"`.
+#[derive(Debug, Clone, Default)]
+pub struct EnvDiff {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    /// (key, old value, new value)
+    pub changed: Vec<(String, String, String)>,
+}
+
+impl EnvDiff {
+    pub fn len(&self) -> usize {
+        self.added.len() + self.removed.len() + self.changed.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Split a qualified key into its scope and variable name
+    pub fn split_key(key: &str) -> (&str, &str) {
+        key.split_once(": ").unwrap_or((SHARED_SCOPE, key))
+    }
+}
+
 /// Manager for multiple environments
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct EnvironmentManager {
     #[serde(default)]
     pub shared: HashMap<String, String>,
+    /// Shared keys whose values are masked in the UI
+    #[serde(default)]
+    pub shared_secret_keys: Vec<String>,
     pub environments: Vec<Environment>,
     pub active_index: Option<usize>,
+    /// Additional files, in load order; all take lower precedence than this file
+    #[serde(skip)]
+    pub additional: Vec<AdditionalEnvFile>,
 }
 
 impl EnvironmentManager {
     pub fn new() -> Self {
         let mut manager = Self {
             shared: HashMap::new(),
+            shared_secret_keys: Vec::new(),
             environments: Vec::new(),
             active_index: None,
+            additional: Vec::new(),
         };
         // Create a default environment
         let mut default_env = Environment::new("default");
@@ -90,6 +149,53 @@ impl EnvironmentManager {
         }
     }
 
+    /// Merge a second environment file under this one. Its shared variables and
+    /// the environment with the same name as the active one fill in any keys
+    /// this file does not define.
+    pub fn load_additional(&mut self, path: &Path) -> Result<()> {
+        let content = std::fs::read_to_string(path)?;
+        let other: EnvironmentManager = serde_json::from_str(&content)?;
+        self.additional.push(AdditionalEnvFile {
+            path: path.to_path_buf(),
+            shared: other.shared,
+            shared_secret_keys: other.shared_secret_keys,
+            environments: other.environments,
+        });
+        Ok(())
+    }
+
+    /// Variables contributed by additional files for the active environment,
+    /// with the file each one comes from. Earlier files win over later ones.
+    pub fn additional_variables(&self) -> Vec<(String, String, &AdditionalEnvFile)> {
+        let active_name = self.active().map(|env| env.name.as_str());
+        let mut merged: Vec<(String, String, &AdditionalEnvFile)> = Vec::new();
+        for file in &self.additional {
+            let env_vars = file
+                .environments
+                .iter()
+                .find(|env| Some(env.name.as_str()) == active_name)
+                .map(|env| &env.variables);
+            for (key, value) in env_vars.into_iter().flatten().chain(&file.shared) {
+                if !merged.iter().any(|(existing, _, _)| existing == key) {
+                    merged.push((key.clone(), value.clone(), file));
+                }
+            }
+        }
+        merged.sort_by(|a, b| a.0.cmp(&b.0));
+        merged
+    }
+
+    /// Whether a merged variable is marked secret in its source file
+    pub fn is_additional_secret(&self, file: &AdditionalEnvFile, key: &str) -> bool {
+        let active_name = self.active_name();
+        file.shared_secret_keys.iter().any(|k| k == key)
+            || file
+                .environments
+                .iter()
+                .filter(|env| env.name == active_name)
+                .any(|env| env.secret_keys.iter().any(|k| k == key))
+    }
+
     /// Save environments to a JSON file
     pub fn save(&self, path: &Path) -> Result<()> {
         let content = serde_json::to_string_pretty(self)?;
@@ -121,16 +227,29 @@ impl EnvironmentManager {
 
     /// Interpolate a string using the active environment
     pub fn interpolate(&self, input: &str) -> String {
-        match self.active() {
-            Some(env) => {
-                let mut combined = self.shared.clone();
-                for (key, value) in &env.variables {
-                    combined.insert(key.clone(), value.clone());
-                }
-                interpolate_with_vars(&combined, input)
+        self.interpolate_with_collection(input, &HashMap::new())
+    }
+
+    /// Interpolate a string using the active environment, falling back to the
+    /// variables of the request's collection for keys no environment defines
+    pub fn interpolate_with_collection(
+        &self,
+        input: &str,
+        collection_vars: &HashMap<String, String>,
+    ) -> String {
+        let mut combined = collection_vars.clone();
+        for (key, value, _) in self.additional_variables() {
+            combined.insert(key, value);
+        }
+        for (key, value) in &self.shared {
+            combined.insert(key.clone(), value.clone());
+        }
+        if let Some(env) = self.active() {
+            for (key, value) in &env.variables {
+                combined.insert(key.clone(), value.clone());
             }
-            None => interpolate_with_vars(&self.shared, input),
         }
+        interpolate_with_vars(&combined, input)
     }
 
     /// Cycle to the next environment
@@ -150,4 +269,149 @@ impl EnvironmentManager {
     pub fn active_color(&self) -> Option<&str> {
         self.active().and_then(|e| e.color.as_deref())
     }
+
+    /// Shared and per-environment variables by qualified key (additional files excluded)
+    fn qualified_variables(&self) -> BTreeMap<String, &String> {
+        let shared = self
+            .shared
+            .iter()
+            .map(|(key, value)| (SHARED_SCOPE, key, value));
+        let per_env = self.environments.iter().flat_map(|env| {
+            env.variables
+                .iter()
+                .map(move |(key, value)| (env.name.as_str(), key, value))
+        });
+        shared
+            .chain(per_env)
+            .map(|(scope, key, value)| (format!("{}: {}", scope, key), value))
+            .collect()
+    }
+
+    /// Variables added, removed or changed in `other` compared to this manager
+    pub fn diff(&self, other: &EnvironmentManager) -> EnvDiff {
+        let old = self.qualified_variables();
+        let new = other.qualified_variables();
+        let mut diff = EnvDiff::default();
+        for (key, value) in &new {
+            match old.get(key) {
+                None => diff.added.push(key.clone()),
+                Some(old_value) if old_value != value => {
+                    diff.changed
+                        .push((key.clone(), old_value.to_string(), value.to_string()));
+                }
+                Some(_) => {}
+            }
+        }
+        diff.removed = old
+            .keys()
+            .filter(|key| !new.contains_key(*key))
+            .cloned()
+            .collect();
+        diff
+    }
+
+    /// Value of a variable by its qualified [`EnvDiff`] key
+    pub fn qualified_value(&self, key: &str) -> Option<&str> {
+        let (scope, name) = EnvDiff::split_key(key);
+        let variables = if scope == SHARED_SCOPE {
+            &self.shared
+        } else {
+            &self
+                .environments
+                .iter()
+                .find(|env| env.name == scope)?
+                .variables
+        };
+        variables.get(name).map(String::as_str)
+    }
+
+    /// Whether a variable, by its qualified [`EnvDiff`] key, is marked secret
+    pub fn is_qualified_secret(&self, key: &str) -> bool {
+        let (scope, name) = EnvDiff::split_key(key);
+        if scope == SHARED_SCOPE {
+            return self.shared_secret_keys.iter().any(|k| k == name);
+        }
+        self.environments
+            .iter()
+            .filter(|env| env.name == scope)
+            .any(|env| env.secret_keys.iter().any(|k| k == name))
+    }
+
+    /// Set (or with `None`, remove) a variable by its qualified [`EnvDiff`] key,
+    /// creating the environment if it does not exist yet
+    pub fn apply_diff_key(&mut self, key: &str, value: Option<&str>) {
+        let (scope, name) = EnvDiff::split_key(key);
+        let variables = if scope == SHARED_SCOPE {
+            &mut self.shared
+        } else {
+            let index = match self.environments.iter().position(|env| env.name == scope) {
+                Some(index) => index,
+                None if value.is_some() => {
+                    self.environments.push(Environment::new(scope));
+                    self.environments.len() - 1
+                }
+                None => return,
+            };
+            &mut self.environments[index].variables
+        };
+        match value {
+            Some(value) => {
+                variables.insert(name.to_string(), value.to_string());
+            }
+            None => {
+                variables.remove(name);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_env_diff() {
+        let mut old = EnvironmentManager::new();
+        old.shared.insert("token".into(), "abc".into());
+        old.shared.insert("region".into(), "eu".into());
+        let mut new = old.clone();
+        new.shared.insert("token".into(), "xyz".into());
+        new.shared.remove("region");
+        new.environments[0].set("timeout", "30");
+        let mut staging = Environment::new("staging");
+        staging.set("base_url", "https://staging.example.com");
+        new.environments.push(staging);
+
+        let diff = old.diff(&new);
+        assert_eq!(diff.added, vec!["default: timeout", "staging: base_url"]);
+        assert_eq!(diff.removed, vec!["(shared): region"]);
+        assert_eq!(
+            diff.changed,
+            vec![(
+                "(shared): token".to_string(),
+                "abc".to_string(),
+                "xyz".to_string()
+            )]
+        );
+        assert_eq!(diff.len(), 4);
+        assert!(old.diff(&old).is_empty());
+
+        // Applying every key of the diff brings the old manager in line with the new one
+        for key in diff.added.iter().chain(&diff.removed) {
+            old.apply_diff_key(key, new.qualified_value(key));
+        }
+        for (key, _, value) in &diff.changed {
+            old.apply_diff_key(key, Some(value));
+        }
+        assert!(old.diff(&new).is_empty());
+        // Removing from an environment that does not exist is a no-op
+        old.apply_diff_key("missing: key", None);
+        assert_eq!(old.environments.len(), 2);
+    }
+
+    #[test]
+    fn test_split_key() {
+        assert_eq!(EnvDiff::split_key("staging: token"), ("staging", "token"));
+        assert_eq!(EnvDiff::split_key("token"), (SHARED_SCOPE, "token"));
+    }
 }
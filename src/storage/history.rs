@@ -13,6 +13,12 @@ pub struct HistoryEntry {
     pub timestamp: DateTime<Utc>,
     pub status_code: Option<u16>,
     pub duration_ms: u64,
+    /// Response body size; absent for failed requests and older history files
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub size_bytes: Option<usize>,
+    /// Text body of the response, for stepping back through past responses
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response_body: Option<String>,
 }
 
 impl HistoryEntry {
@@ -23,9 +29,21 @@ impl HistoryEntry {
             timestamp: Utc::now(),
             status_code,
             duration_ms,
+            size_bytes: None,
+            response_body: None,
         }
     }
 
+    pub fn with_size_bytes(mut self, size_bytes: usize) -> Self {
+        self.size_bytes = Some(size_bytes);
+        self
+    }
+
+    pub fn with_response_body(mut self, body: String) -> Self {
+        self.response_body = Some(body);
+        self
+    }
+
     /// Format for display in the history list
     pub fn display(&self) -> String {
         let status = self
@@ -87,6 +105,12 @@ impl HistoryManager {
         }
     }
 
+    /// Change how many entries are kept, dropping the oldest if needed
+    pub fn set_max_entries(&mut self, max_entries: usize) {
+        self.max_entries = max_entries;
+        self.entries.truncate(max_entries);
+    }
+
     /// Get recent entries (most recent first)
     pub fn recent(&self, count: usize) -> &[HistoryEntry] {
         let end = count.min(self.entries.len());
@@ -97,4 +121,68 @@ impl HistoryManager {
     pub fn clear(&mut self) {
         self.entries.clear();
     }
+
+    /// Remove entries recorded before the cutoff, returning how many were dropped
+    pub fn clear_before(&mut self, cutoff: DateTime<Utc>) -> usize {
+        let before = self.entries.len();
+        self.entries.retain(|entry| entry.timestamp >= cutoff);
+        before - self.entries.len()
+    }
+
+    /// Remove entries whose URL starts with the given prefix, returning how many were dropped
+    pub fn clear_for_url(&mut self, url: &str) -> usize {
+        let before = self.entries.len();
+        self.entries
+            .retain(|entry| !entry.request.url.starts_with(url));
+        before - self.entries.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn entry(url: &str, age_days: i64) -> HistoryEntry {
+        let mut request = ApiRequest::new("test");
+        request.url = url.to_string();
+        let mut entry = HistoryEntry::new(request, Some(200), 10);
+        entry.timestamp = Utc::now() - Duration::days(age_days);
+        entry
+    }
+
+    #[test]
+    fn test_clear_before_and_for_url() {
+        let mut history = HistoryManager::new();
+        history.entries = vec![
+            entry("https://api.test/users/1", 0),
+            entry("https://api.test/users?page=2", 3),
+            entry("https://api.test/orders", 10),
+        ];
+
+        assert_eq!(history.clear_before(Utc::now() - Duration::days(7)), 1);
+        assert_eq!(history.entries.len(), 2);
+
+        assert_eq!(history.clear_for_url("https://api.test/users"), 2);
+        assert!(history.entries.is_empty());
+    }
+
+    #[test]
+    fn test_response_fields_round_trip() {
+        let entry = entry("https://api.test/users", 0)
+            .with_size_bytes(7)
+            .with_response_body("{\"a\":1}".to_string());
+        let json = serde_json::to_value(&entry).unwrap();
+        let restored: HistoryEntry = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(restored.size_bytes, Some(7));
+        assert_eq!(restored.response_body.as_deref(), Some("{\"a\":1}"));
+
+        // Entries saved before these fields existed still load
+        let mut old = json;
+        old.as_object_mut().unwrap().remove("size_bytes");
+        old.as_object_mut().unwrap().remove("response_body");
+        let restored: HistoryEntry = serde_json::from_value(old).unwrap();
+        assert_eq!(restored.size_bytes, None);
+        assert_eq!(restored.response_body, None);
+    }
 }
@@ -4,8 +4,12 @@ pub mod history;
 pub mod request;
 pub mod settings;
 
-pub use collection::{Collection, CollectionItem};
-pub use environment::EnvironmentManager;
+pub use collection::{Collection, CollectionItem, MergeResult};
+pub use environment::{EnvDiff, EnvironmentManager};
 pub use history::{HistoryEntry, HistoryManager};
-pub use request::{ApiRequest, AuthConfig, AuthType, HttpMethod, KeyValue};
-pub use settings::Settings;
+pub use request::{
+    build_url, headers_to_json, is_path_variable, json_to_headers, parse_curl, parse_url_segments,
+    url_decode, url_encode, ApiRequest, AuthConfig, AuthType, BodyType, CompressionType,
+    HttpMethod, KeyValue, OAuth2Config, OAuth2Field, UrlSegmentField, UrlSegments,
+};
+pub use settings::{HeaderPreset, JqPreset, Settings};
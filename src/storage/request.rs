@@ -1,4 +1,7 @@
+use anyhow::{bail, Result};
+use percent_encoding::{percent_decode_str, utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use uuid::Uuid;
 
 /// HTTP methods supported by the application
@@ -11,6 +14,7 @@ pub enum HttpMethod {
     Put,
     Patch,
     Delete,
+    Head,
 }
 
 impl HttpMethod {
@@ -21,6 +25,7 @@ impl HttpMethod {
             HttpMethod::Put => "PUT",
             HttpMethod::Patch => "PATCH",
             HttpMethod::Delete => "DELETE",
+            HttpMethod::Head => "HEAD",
         }
     }
 
@@ -31,6 +36,7 @@ impl HttpMethod {
             HttpMethod::Put,
             HttpMethod::Patch,
             HttpMethod::Delete,
+            HttpMethod::Head,
         ]
     }
 
@@ -40,17 +46,19 @@ impl HttpMethod {
             HttpMethod::Post => HttpMethod::Put,
             HttpMethod::Put => HttpMethod::Patch,
             HttpMethod::Patch => HttpMethod::Delete,
-            HttpMethod::Delete => HttpMethod::Get,
+            HttpMethod::Delete => HttpMethod::Head,
+            HttpMethod::Head => HttpMethod::Get,
         }
     }
 
     pub fn prev(&self) -> HttpMethod {
         match self {
-            HttpMethod::Get => HttpMethod::Delete,
+            HttpMethod::Get => HttpMethod::Head,
             HttpMethod::Post => HttpMethod::Get,
             HttpMethod::Put => HttpMethod::Post,
             HttpMethod::Patch => HttpMethod::Put,
             HttpMethod::Delete => HttpMethod::Patch,
+            HttpMethod::Head => HttpMethod::Delete,
         }
     }
 }
@@ -88,6 +96,9 @@ pub enum AuthType {
     Bearer,
     Basic,
     ApiKey,
+    /// OAuth2 authorization code flow with PKCE; sends the obtained token as a Bearer token
+    #[serde(rename = "oauth2_pkce")]
+    OAuth2Pkce,
 }
 
 impl AuthType {
@@ -97,6 +108,7 @@ impl AuthType {
             AuthType::Bearer => "Bearer Token",
             AuthType::Basic => "Basic Auth",
             AuthType::ApiKey => "API Key",
+            AuthType::OAuth2Pkce => "OAuth2 PKCE",
         }
     }
 
@@ -106,6 +118,7 @@ impl AuthType {
             AuthType::Bearer,
             AuthType::Basic,
             AuthType::ApiKey,
+            AuthType::OAuth2Pkce,
         ]
     }
 
@@ -114,7 +127,72 @@ impl AuthType {
             AuthType::None => AuthType::Bearer,
             AuthType::Bearer => AuthType::Basic,
             AuthType::Basic => AuthType::ApiKey,
-            AuthType::ApiKey => AuthType::None,
+            AuthType::ApiKey => AuthType::OAuth2Pkce,
+            AuthType::OAuth2Pkce => AuthType::None,
+        }
+    }
+}
+
+/// How the request body is interpreted and which Content-Type it implies
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BodyType {
+    #[default]
+    Raw,
+    FormUrlEncoded,
+    GraphQL,
+}
+
+impl BodyType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BodyType::Raw => "Raw",
+            BodyType::FormUrlEncoded => "Form URL-Encoded",
+            BodyType::GraphQL => "GraphQL",
+        }
+    }
+
+    pub fn next(&self) -> BodyType {
+        match self {
+            BodyType::Raw => BodyType::FormUrlEncoded,
+            BodyType::FormUrlEncoded => BodyType::GraphQL,
+            BodyType::GraphQL => BodyType::Raw,
+        }
+    }
+
+    /// Content-Type header managed automatically while this body type is active
+    pub fn content_type(&self) -> Option<&'static str> {
+        match self {
+            BodyType::Raw => None,
+            BodyType::FormUrlEncoded => Some("application/x-www-form-urlencoded"),
+            BodyType::GraphQL => Some("application/graphql"),
+        }
+    }
+}
+
+/// Compression applied to the request body at send time
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CompressionType {
+    Gzip,
+    Deflate,
+}
+
+impl CompressionType {
+    /// Value of the Content-Encoding header
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CompressionType::Gzip => "gzip",
+            CompressionType::Deflate => "deflate",
+        }
+    }
+
+    /// Cycle None → Gzip → Deflate → None
+    pub fn cycle(current: Option<CompressionType>) -> Option<CompressionType> {
+        match current {
+            None => Some(CompressionType::Gzip),
+            Some(CompressionType::Gzip) => Some(CompressionType::Deflate),
+            Some(CompressionType::Deflate) => None,
         }
     }
 }
@@ -135,6 +213,84 @@ pub struct AuthConfig {
     pub api_key_value: String,
     /// Where to send API key: "header" or "query"
     pub api_key_location: String,
+    /// OAuth2 PKCE endpoints and the token it last obtained
+    #[serde(default)]
+    pub oauth2: OAuth2Config,
+}
+
+/// OAuth2 authorization code flow with PKCE (authorized with 'o' in the Auth tab)
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct OAuth2Config {
+    pub auth_url: String,
+    pub token_url: String,
+    pub client_id: String,
+    /// Space-separated scopes, may be empty
+    pub scope: String,
+    /// Local address the browser is sent back to; http://localhost:9999/callback if empty
+    pub redirect_uri: String,
+    /// Token from the last successful authorization
+    pub access_token: String,
+}
+
+/// One editable field of [`OAuth2Config`], in display (and Tab) order
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OAuth2Field {
+    AuthUrl,
+    TokenUrl,
+    ClientId,
+    Scope,
+    RedirectUri,
+}
+
+impl OAuth2Field {
+    pub const ALL: [OAuth2Field; 5] = [
+        OAuth2Field::AuthUrl,
+        OAuth2Field::TokenUrl,
+        OAuth2Field::ClientId,
+        OAuth2Field::Scope,
+        OAuth2Field::RedirectUri,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            OAuth2Field::AuthUrl => "Auth URL: ",
+            OAuth2Field::TokenUrl => "Token URL: ",
+            OAuth2Field::ClientId => "Client ID: ",
+            OAuth2Field::Scope => "Scope: ",
+            OAuth2Field::RedirectUri => "Redirect URI: ",
+        }
+    }
+
+    pub fn next(&self) -> OAuth2Field {
+        let index = Self::ALL
+            .iter()
+            .position(|field| field == self)
+            .unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+}
+
+impl OAuth2Config {
+    pub fn get(&self, field: OAuth2Field) -> &String {
+        match field {
+            OAuth2Field::AuthUrl => &self.auth_url,
+            OAuth2Field::TokenUrl => &self.token_url,
+            OAuth2Field::ClientId => &self.client_id,
+            OAuth2Field::Scope => &self.scope,
+            OAuth2Field::RedirectUri => &self.redirect_uri,
+        }
+    }
+
+    pub fn get_mut(&mut self, field: OAuth2Field) -> &mut String {
+        match field {
+            OAuth2Field::AuthUrl => &mut self.auth_url,
+            OAuth2Field::TokenUrl => &mut self.token_url,
+            OAuth2Field::ClientId => &mut self.client_id,
+            OAuth2Field::Scope => &mut self.scope,
+            OAuth2Field::RedirectUri => &mut self.redirect_uri,
+        }
+    }
 }
 
 /// Represents an API request
@@ -147,7 +303,33 @@ pub struct ApiRequest {
     pub headers: Vec<KeyValue>,
     pub query_params: Vec<KeyValue>,
     pub body: String,
+    #[serde(default)]
+    pub body_type: BodyType,
+    /// JSON variables sent along with the query when `body_type` is GraphQL
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub graphql_variables: String,
+    /// Compress the body when sending (the stored body stays uncompressed)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compress_body: Option<CompressionType>,
     pub auth: AuthConfig,
+    /// Send the collection's default auth instead of `auth`
+    #[serde(default)]
+    pub use_collection_auth: bool,
+    /// Cache GET responses for this long instead of the server-provided TTL
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache_ttl_override_ms: Option<u64>,
+    /// Proxy URL for this request only; `"none"` bypasses every proxy
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proxy_override: Option<String>,
+    /// JSON Schema the response body is checked against (V in the response view)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response_schema: Option<String>,
+    /// Starred with '*' in the request list
+    #[serde(default)]
+    pub favorite: bool,
+    /// Shown in the footer while the mouse is over the request in the list
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
 }
 
 impl Default for ApiRequest {
@@ -160,7 +342,16 @@ impl Default for ApiRequest {
             headers: vec![KeyValue::new("Content-Type", "application/json")],
             query_params: Vec::new(),
             body: String::new(),
+            body_type: BodyType::Raw,
+            graphql_variables: String::new(),
+            compress_body: None,
             auth: AuthConfig::default(),
+            use_collection_auth: false,
+            cache_ttl_override_ms: None,
+            proxy_override: None,
+            response_schema: None,
+            favorite: false,
+            description: None,
         }
     }
 }
@@ -173,6 +364,31 @@ impl ApiRequest {
         }
     }
 
+    /// Content-Type the body is sent with in place of the request's own. A
+    /// GraphQL query with variables can't go out as `application/graphql`, so
+    /// it is sent as a JSON payload instead.
+    pub fn sent_content_type(&self) -> Option<&'static str> {
+        (self.body_type == BodyType::GraphQL && !self.graphql_variables.trim().is_empty())
+            .then_some("application/json")
+    }
+
+    /// The body as it is sent, after `interpolate`: form fields are URL-encoded
+    /// and a GraphQL query with variables is wrapped in a `query`/`variables`
+    /// JSON payload. Raw bodies are sent as they are.
+    pub fn encoded_body(&self, interpolate: impl Fn(&str) -> String) -> Result<String> {
+        let body = interpolate(&self.body);
+        match self.body_type {
+            BodyType::Raw => Ok(body),
+            BodyType::FormUrlEncoded => Ok(encode_form_body(&body)),
+            BodyType::GraphQL if self.sent_content_type().is_some() => {
+                let variables: Value = serde_json::from_str(&interpolate(&self.graphql_variables))
+                    .map_err(|e| anyhow::anyhow!("GraphQL variables are not valid JSON: {}", e))?;
+                Ok(serde_json::json!({ "query": body, "variables": variables }).to_string())
+            }
+            BodyType::GraphQL => Ok(body),
+        }
+    }
+
     /// Get a display name for the request (method + path or name)
     pub fn display_name(&self) -> String {
         if self.url.is_empty() {
@@ -190,3 +406,534 @@ impl ApiRequest {
         }
     }
 }
+
+/// A URL split into the parts edited by the URL builder
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UrlSegments {
+    pub scheme: String,
+    pub host: String,
+    pub path: Vec<String>,
+    pub query: Vec<(String, String)>,
+    /// Kept so rebuilding never drops it; not edited in the builder
+    pub fragment: Option<String>,
+}
+
+/// One editable field of [`UrlSegments`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UrlSegmentField {
+    Scheme,
+    Host,
+    Path(usize),
+    QueryKey(usize),
+    QueryValue(usize),
+}
+
+impl UrlSegments {
+    /// Fields in display (and Tab) order
+    pub fn fields(&self) -> Vec<UrlSegmentField> {
+        let mut fields = vec![UrlSegmentField::Scheme, UrlSegmentField::Host];
+        fields.extend((0..self.path.len()).map(UrlSegmentField::Path));
+        for i in 0..self.query.len() {
+            fields.push(UrlSegmentField::QueryKey(i));
+            fields.push(UrlSegmentField::QueryValue(i));
+        }
+        fields
+    }
+
+    pub fn get(&self, field: UrlSegmentField) -> Option<&String> {
+        match field {
+            UrlSegmentField::Scheme => Some(&self.scheme),
+            UrlSegmentField::Host => Some(&self.host),
+            UrlSegmentField::Path(i) => self.path.get(i),
+            UrlSegmentField::QueryKey(i) => self.query.get(i).map(|(key, _)| key),
+            UrlSegmentField::QueryValue(i) => self.query.get(i).map(|(_, value)| value),
+        }
+    }
+
+    pub fn get_mut(&mut self, field: UrlSegmentField) -> Option<&mut String> {
+        match field {
+            UrlSegmentField::Scheme => Some(&mut self.scheme),
+            UrlSegmentField::Host => Some(&mut self.host),
+            UrlSegmentField::Path(i) => self.path.get_mut(i),
+            UrlSegmentField::QueryKey(i) => self.query.get_mut(i).map(|(key, _)| key),
+            UrlSegmentField::QueryValue(i) => self.query.get_mut(i).map(|(_, value)| value),
+        }
+    }
+}
+
+/// Whether a path segment is a REST-style path variable such as `{id}`
+pub fn is_path_variable(segment: &str) -> bool {
+    segment.len() > 2
+        && segment.starts_with('{')
+        && segment.ends_with('}')
+        && !segment.starts_with("{{")
+}
+
+/// Split a URL into scheme, host, path segments and query pairs. Text is kept
+/// as typed (no percent-decoding) so `build_url` gives the same URL back.
+pub fn parse_url_segments(url: &str) -> UrlSegments {
+    let (scheme, rest) = match url.split_once("://") {
+        Some((scheme, rest)) => (scheme.to_string(), rest),
+        None => (String::new(), url),
+    };
+    let (rest, fragment) = match rest.split_once('#') {
+        Some((rest, fragment)) => (rest, Some(fragment.to_string())),
+        None => (rest, None),
+    };
+    let (location, query) = match rest.split_once('?') {
+        Some((location, query)) => (location, Some(query)),
+        None => (rest, None),
+    };
+    let (host, path) = match location.split_once('/') {
+        Some((host, path)) => (host, path.split('/').map(String::from).collect()),
+        None => (location, Vec::new()),
+    };
+    let query = query
+        .map(|query| {
+            query
+                .split('&')
+                .filter(|pair| !pair.is_empty())
+                .map(|pair| match pair.split_once('=') {
+                    Some((key, value)) => (key.to_string(), value.to_string()),
+                    None => (pair.to_string(), String::new()),
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    UrlSegments {
+        scheme,
+        host: host.to_string(),
+        path,
+        query,
+        fragment,
+    }
+}
+
+/// Join URL segments back into a URL string
+pub fn build_url(segments: &UrlSegments) -> String {
+    let mut url = String::new();
+    if !segments.scheme.is_empty() {
+        url.push_str(&segments.scheme);
+        url.push_str("://");
+    }
+    url.push_str(&segments.host);
+    for segment in &segments.path {
+        url.push('/');
+        url.push_str(segment);
+    }
+    if !segments.query.is_empty() {
+        let pairs: Vec<String> = segments
+            .query
+            .iter()
+            .map(|(key, value)| {
+                if value.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}={}", key, value)
+                }
+            })
+            .collect();
+        url.push('?');
+        url.push_str(&pairs.join("&"));
+    }
+    if let Some(fragment) = &segments.fragment {
+        url.push('#');
+        url.push_str(fragment);
+    }
+    url
+}
+
+/// Characters left as-is by `url_encode`: the RFC 3986 unreserved set
+const URL_COMPONENT: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'_')
+    .remove(b'.')
+    .remove(b'~');
+
+/// Percent-encode text for use as a single URL component (path segment or query value)
+pub fn url_encode(text: &str) -> String {
+    utf8_percent_encode(text, URL_COMPONENT).to_string()
+}
+
+/// Decode percent escapes; invalid UTF-8 is replaced and `+` is left as-is
+pub fn url_decode(text: &str) -> String {
+    percent_decode_str(text).decode_utf8_lossy().into_owned()
+}
+
+/// Encode a form body for `application/x-www-form-urlencoded`. Fields are
+/// `key=value` pairs separated by newlines or `&`; keys and values may be
+/// typed as plain text or already percent-encoded, and are sent encoded once.
+/// Blank lines and lines starting with `#` are skipped.
+pub fn encode_form_body(body: &str) -> String {
+    body.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .flat_map(|line| line.split('&'))
+        .filter(|field| !field.is_empty())
+        .map(|field| {
+            let (key, value) = field.split_once('=').unwrap_or((field, ""));
+            format!(
+                "{}={}",
+                url_encode(&url_decode(key.trim())),
+                url_encode(&url_decode(value.trim()))
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+/// Headers as a JSON object of name to value. Disabled headers are omitted:
+/// the object has nowhere to keep the flag, and pasting them back would turn
+/// them on again.
+pub fn headers_to_json(headers: &[KeyValue]) -> String {
+    let map: serde_json::Map<String, Value> = headers
+        .iter()
+        .filter(|header| header.enabled && !header.key.is_empty())
+        .map(|header| (header.key.clone(), Value::String(header.value.clone())))
+        .collect();
+    serde_json::to_string_pretty(&Value::Object(map)).unwrap_or_default()
+}
+
+/// Parse a JSON object of name to value into headers; non-string values are
+/// kept as their JSON text
+pub fn json_to_headers(json: &str) -> Result<Vec<KeyValue>> {
+    let Value::Object(map) = serde_json::from_str(json.trim())? else {
+        bail!("Expected a JSON object of headers");
+    };
+    Ok(map
+        .into_iter()
+        .map(|(key, value)| match value {
+            Value::String(value) => KeyValue::new(key, value),
+            other => KeyValue::new(key, other.to_string()),
+        })
+        .collect())
+}
+
+/// Split a shell command line into words, honoring single and double quotes,
+/// backslash escapes and `\` line continuations
+fn shell_words(command: &str) -> Result<Vec<String>> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut in_word = false;
+    let mut chars = command.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' => {
+                in_word = true;
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(c) => word.push(c),
+                        None => bail!("Unterminated ' quote"),
+                    }
+                }
+            }
+            '"' => {
+                in_word = true;
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some(c @ ('"' | '\\' | '$' | '`')) => word.push(c),
+                            Some('\n') => {}
+                            Some(c) => {
+                                word.push('\\');
+                                word.push(c);
+                            }
+                            None => bail!("Unterminated \" quote"),
+                        },
+                        Some(c) => word.push(c),
+                        None => bail!("Unterminated \" quote"),
+                    }
+                }
+            }
+            '\\' => match chars.next() {
+                // Line continuation
+                Some('\n') => {}
+                Some('\r') if chars.peek() == Some(&'\n') => {
+                    chars.next();
+                }
+                Some(c) => {
+                    in_word = true;
+                    word.push(c);
+                }
+                None => {}
+            },
+            c if c.is_whitespace() => {
+                if in_word {
+                    words.push(std::mem::take(&mut word));
+                    in_word = false;
+                }
+            }
+            c => {
+                in_word = true;
+                word.push(c);
+            }
+        }
+    }
+    if in_word {
+        words.push(word);
+    }
+    Ok(words)
+}
+
+/// Build a request from a curl command line. Supports the method, headers,
+/// body, basic auth, `--json`, `-I` and the URL; other flags are ignored.
+/// The request is named after the last path segment of the URL.
+pub fn parse_curl(command: &str) -> Result<ApiRequest> {
+    let words = shell_words(command.trim())?;
+    let mut words = words.into_iter();
+    if words.next().as_deref() != Some("curl") {
+        bail!("Not a curl command");
+    }
+
+    let mut request = ApiRequest {
+        headers: Vec::new(),
+        ..Default::default()
+    };
+    let mut method = None;
+    let mut url = None;
+    let mut data: Vec<String> = Vec::new();
+    let mut json = false;
+
+    while let Some(word) = words.next() {
+        // Short options may carry their value attached: -XPOST, -H'Accept: */*'
+        let (flag, attached) = match word.strip_prefix('-') {
+            Some(rest) if !rest.starts_with('-') && rest.len() > 1 => {
+                let (flag, value) = word.split_at(2);
+                if matches!(flag, "-X" | "-H" | "-d" | "-u" | "-A" | "-b" | "-e") {
+                    (flag.to_string(), Some(value.to_string()))
+                } else {
+                    (word.clone(), None)
+                }
+            }
+            _ => (word.clone(), None),
+        };
+        let mut value = || -> Result<String> {
+            match attached.clone() {
+                Some(value) => Ok(value),
+                None => words
+                    .next()
+                    .ok_or_else(|| anyhow::anyhow!("{} needs a value", flag)),
+            }
+        };
+
+        match flag.as_str() {
+            "-X" | "--request" => method = Some(value()?),
+            "-H" | "--header" => {
+                let header = value()?;
+                if let Some((key, val)) = header.split_once(':') {
+                    request.headers.push(KeyValue::new(key.trim(), val.trim()));
+                }
+            }
+            "-d" | "--data" | "--data-raw" | "--data-binary" | "--data-ascii"
+            | "--data-urlencode" => data.push(value()?),
+            "--json" => {
+                data.push(value()?);
+                json = true;
+            }
+            "-u" | "--user" => {
+                let credentials = value()?;
+                let (user, pass) = credentials.split_once(':').unwrap_or((&credentials, ""));
+                request.auth = AuthConfig {
+                    auth_type: AuthType::Basic,
+                    basic_username: user.to_string(),
+                    basic_password: pass.to_string(),
+                    ..Default::default()
+                };
+            }
+            "-A" | "--user-agent" => request.headers.push(KeyValue::new("User-Agent", value()?)),
+            "-b" | "--cookie" => request.headers.push(KeyValue::new("Cookie", value()?)),
+            "-e" | "--referer" => request.headers.push(KeyValue::new("Referer", value()?)),
+            "-I" | "--head" => method = Some("HEAD".to_string()),
+            "--url" => url = Some(value()?),
+            // Flags that take a value we have no use for
+            "-o" | "--output" | "-m" | "--max-time" | "--connect-timeout" | "-w"
+            | "--write-out" | "-x" | "--proxy" | "--cacert" | "--cert" | "--key" | "-F"
+            | "--form" => {
+                value()?;
+            }
+            flag if flag.starts_with('-') && flag.len() > 1 => {}
+            _ => {
+                if url.is_none() {
+                    url = Some(word);
+                }
+            }
+        }
+    }
+
+    let Some(url) = url else {
+        bail!("No URL in curl command");
+    };
+    let has_header = |request: &ApiRequest, name: &str| {
+        request
+            .headers
+            .iter()
+            .any(|header| header.key.eq_ignore_ascii_case(name))
+    };
+    if !data.is_empty() {
+        request.body = data.join("&");
+        if json {
+            if !has_header(&request, "Content-Type") {
+                request
+                    .headers
+                    .push(KeyValue::new("Content-Type", "application/json"));
+            }
+            if !has_header(&request, "Accept") {
+                request
+                    .headers
+                    .push(KeyValue::new("Accept", "application/json"));
+            }
+        } else if !has_header(&request, "Content-Type") {
+            // What curl sends for -d without an explicit type
+            request.headers.push(KeyValue::new(
+                "Content-Type",
+                "application/x-www-form-urlencoded",
+            ));
+        }
+    }
+
+    request.method = match method {
+        Some(method) => *HttpMethod::all()
+            .iter()
+            .find(|m| m.as_str().eq_ignore_ascii_case(&method))
+            .ok_or_else(|| anyhow::anyhow!("Unsupported method: {}", method))?,
+        None if !data.is_empty() => HttpMethod::Post,
+        None => HttpMethod::Get,
+    };
+    request.name = parse_url_segments(&url)
+        .path
+        .last()
+        .filter(|segment| !segment.is_empty())
+        .cloned()
+        .unwrap_or_else(|| "Imported Request".to_string());
+    request.url = url;
+    Ok(request)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_url_segments_round_trip() {
+        let url = "https://{{host}}/api/users/{id}/posts?page=2&sort=desc%20date#top";
+        let segments = parse_url_segments(url);
+        assert_eq!(segments.scheme, "https");
+        assert_eq!(segments.host, "{{host}}");
+        assert_eq!(segments.path, vec!["api", "users", "{id}", "posts"]);
+        assert_eq!(segments.query[1], ("sort".into(), "desc%20date".into()));
+        assert!(is_path_variable(&segments.path[2]));
+        assert!(!is_path_variable("{{host}}"));
+        assert_eq!(build_url(&segments), url);
+
+        assert_eq!(
+            build_url(&parse_url_segments("localhost:3000/")),
+            "localhost:3000/"
+        );
+    }
+
+    #[test]
+    fn test_headers_json_round_trip() {
+        let mut disabled = KeyValue::new("X-Debug", "1");
+        disabled.enabled = false;
+        let headers = vec![
+            KeyValue::new("Accept", "application/json"),
+            disabled,
+            KeyValue::new("Authorization", "Bearer abc"),
+        ];
+
+        let parsed = json_to_headers(&headers_to_json(&headers)).unwrap();
+        let pairs: Vec<(&str, &str)> = parsed
+            .iter()
+            .map(|header| (header.key.as_str(), header.value.as_str()))
+            .collect();
+        assert_eq!(
+            pairs,
+            vec![
+                ("Accept", "application/json"),
+                ("Authorization", "Bearer abc")
+            ]
+        );
+
+        assert_eq!(
+            json_to_headers(r#"{"X-Retries": 3}"#).unwrap()[0].value,
+            "3"
+        );
+        assert!(json_to_headers("[1, 2]").is_err());
+    }
+
+    #[test]
+    fn test_parse_curl() {
+        let request = parse_curl(
+            "curl -X PUT 'https://api.example.com/users/42?verbose=1' \\\n  -H 'Accept: application/json' \\\n  -H \"Authorization: Bearer abc\" \\\n  --data-raw '{\"name\": \"Ada\"}' -sSL",
+        )
+        .unwrap();
+        assert_eq!(request.method, HttpMethod::Put);
+        assert_eq!(request.url, "https://api.example.com/users/42?verbose=1");
+        assert_eq!(request.name, "42");
+        assert_eq!(request.body, r#"{"name": "Ada"}"#);
+        let headers: Vec<(&str, &str)> = request
+            .headers
+            .iter()
+            .map(|header| (header.key.as_str(), header.value.as_str()))
+            .collect();
+        assert_eq!(
+            headers,
+            vec![
+                ("Accept", "application/json"),
+                ("Authorization", "Bearer abc"),
+                ("Content-Type", "application/x-www-form-urlencoded"),
+            ]
+        );
+
+        let request = parse_curl("curl -u ada:s3cret --json '{}' https://example.com").unwrap();
+        assert_eq!(request.method, HttpMethod::Post);
+        assert_eq!(request.auth.auth_type, AuthType::Basic);
+        assert_eq!(request.auth.basic_password, "s3cret");
+        assert_eq!(request.headers[0].value, "application/json");
+        assert_eq!(request.name, "Imported Request");
+
+        assert_eq!(
+            parse_curl("curl -XDELETE example.com/a").unwrap().method,
+            HttpMethod::Delete
+        );
+        assert!(parse_curl("wget example.com").is_err());
+        assert!(parse_curl("curl -H 'Accept: */*'").is_err());
+        assert!(parse_curl("curl 'example.com").is_err());
+    }
+
+    #[test]
+    fn test_encoded_body() {
+        assert_eq!(
+            encode_form_body("name=Ada Lovelace\n# comment\n\nnote=a&b%26c=1&empty"),
+            "name=Ada%20Lovelace&note=a&b%26c=1&empty="
+        );
+
+        let mut request = ApiRequest::new("Query");
+        request.body_type = BodyType::GraphQL;
+        request.body = "query($id: ID!) { user(id: $id) { name } }".to_string();
+        let interpolate = |s: &str| s.replace("{{id}}", "42");
+        assert_eq!(request.sent_content_type(), None);
+        assert_eq!(request.encoded_body(interpolate).unwrap(), request.body);
+
+        request.graphql_variables = r#"{"id": "{{id}}"}"#.to_string();
+        assert_eq!(request.sent_content_type(), Some("application/json"));
+        let payload: Value =
+            serde_json::from_str(&request.encoded_body(interpolate).unwrap()).unwrap();
+        assert_eq!(payload["query"], request.body.as_str());
+        assert_eq!(payload["variables"]["id"], "42");
+
+        request.graphql_variables = "{ id: 1 }".to_string();
+        assert!(request.encoded_body(interpolate).is_err());
+    }
+
+    #[test]
+    fn test_url_encode_decode() {
+        assert_eq!(url_encode("a b&c=d/é~"), "a%20b%26c%3Dd%2F%C3%A9~");
+        assert_eq!(url_decode("a%20b%26c%3Dd%2F%C3%A9~"), "a b&c=d/é~");
+        assert_eq!(url_decode("100%+sure"), "100%+sure");
+        assert_eq!(url_decode(&url_encode("{{token}}")), "{{token}}");
+    }
+}
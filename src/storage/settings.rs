@@ -1,16 +1,195 @@
+use super::request::KeyValue;
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
+/// A named set of headers that can be added to a request in one go
 #[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HeaderPreset {
+    pub name: String,
+    pub headers: Vec<KeyValue>,
+}
+
+impl HeaderPreset {
+    pub fn new(name: impl Into<String>, headers: &[(&str, &str)]) -> Self {
+        Self {
+            name: name.into(),
+            headers: headers
+                .iter()
+                .map(|(key, value)| KeyValue::new(*key, *value))
+                .collect(),
+        }
+    }
+
+    /// Presets available before the user saves any of their own
+    pub fn defaults() -> Vec<Self> {
+        vec![
+            Self::new(
+                "JSON API",
+                &[
+                    ("Content-Type", "application/json"),
+                    ("Accept", "application/json"),
+                ],
+            ),
+            Self::new(
+                "Form",
+                &[("Content-Type", "application/x-www-form-urlencoded")],
+            ),
+            Self::new(
+                "GraphQL",
+                &[
+                    ("Content-Type", "application/graphql"),
+                    ("Accept", "application/json"),
+                ],
+            ),
+            Self::new(
+                "No Cache",
+                &[("Cache-Control", "no-cache"), ("Pragma", "no-cache")],
+            ),
+            Self::new(
+                "CORS preflight",
+                &[
+                    ("Origin", "http://localhost:3000"),
+                    ("Access-Control-Request-Method", "POST"),
+                    ("Access-Control-Request-Headers", "Content-Type"),
+                ],
+            ),
+        ]
+    }
+
+    /// Load presets from a JSON file, or the defaults when there is no file yet
+    pub fn load_all(path: &Path) -> Result<Vec<Self>> {
+        if !path.exists() {
+            return Ok(Self::defaults());
+        }
+        let content = std::fs::read_to_string(path)?;
+        let presets = serde_json::from_str(&content)?;
+        Ok(presets)
+    }
+
+    pub fn save_all(presets: &[Self], path: &Path) -> Result<()> {
+        let content = serde_json::to_string_pretty(presets)?;
+        std::fs::write(path, content)?;
+        Ok(())
+    }
+}
+
+/// A named jq filter kept for reuse on other responses
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JqPreset {
+    pub name: String,
+    pub filter: String,
+    #[serde(default)]
+    pub description: String,
+}
+
+impl JqPreset {
+    pub fn new(name: &str, filter: &str, description: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            filter: filter.to_string(),
+            description: description.to_string(),
+        }
+    }
+
+    /// Presets available before the user saves any of their own
+    pub fn defaults() -> Vec<Self> {
+        vec![
+            Self::new("Identity", ".", "The whole document"),
+            Self::new("IDs", ".[] | .id", "The id of every element"),
+            Self::new("Keys", "keys", "Keys of an object, sorted"),
+            Self::new("Count", "[.[]] | length", "Number of elements or values"),
+            Self::new("Entries", "to_entries", "Object as key/value pairs"),
+        ]
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Settings {
     pub theme: String,
+    /// Number of history entries to keep
+    pub max_history: usize,
+    /// Request timeout in milliseconds
+    pub default_timeout_ms: u64,
+    pub follow_redirects: bool,
+    pub verify_ssl: bool,
+    /// Show a line number gutter in the response view
+    pub show_line_numbers: bool,
+    /// Truncate the displayed response body past this size (0 = no limit)
+    pub max_response_display_bytes: usize,
+    /// Response size shown as the full mark of the size gauge (0 = no gauge)
+    pub response_size_warning_bytes: usize,
+    /// Width of the request list, in percent of the terminal
+    pub layout_split_h: u16,
+    /// Height of the request editor, in percent of the right column
+    pub layout_split_v: u16,
+    /// Serve repeated GETs from an in-memory cache while their TTL lasts
+    pub cache_enabled: bool,
+    /// Hold back sends while the server's rate limit is used up, then send automatically
+    pub auto_throttle: bool,
+    /// Pretty-print JSON bodies when saving a request
+    pub auto_format_on_save: bool,
+    /// Pretty-print JSON bodies in the request that is sent, leaving the stored one as is
+    pub auto_format_on_send: bool,
+    /// Overview column beside long responses
+    pub show_minimap: bool,
+    /// Spaces per level when pretty-printing JSON responses (2 or 4)
+    pub pretty_indent: u8,
+    /// Indent new lines in a JSON body to the current nesting depth
+    pub auto_indent: bool,
+    /// Check responses against the request's JSON Schema as they arrive
+    pub auto_validate_schema: bool,
+    /// Look for a newer release on GitHub at startup (at most once a day)
+    pub check_for_updates: bool,
+    /// Block edits to the loaded request and the collections (Ctrl+Shift+R)
+    pub readonly_mode: bool,
+    /// Write detailed request/response logs to a file (read at startup)
+    pub log_to_file: bool,
+    /// Log file to use instead of restui.log in the config directory
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub log_file_path: Option<PathBuf>,
+    /// jq filter re-applied to every response until unpinned (Ctrl+Shift+F)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub persistent_filter: Option<String>,
+    /// Extra environment files merged under environments.json, in order
+    pub additional_env_files: Vec<PathBuf>,
+    /// jq filters offered by the preset picker (Ctrl+p while typing a filter)
+    pub jq_presets: Vec<JqPreset>,
+    /// Header presets, stored separately in header_presets.json
+    #[serde(skip)]
+    pub header_presets: Vec<HeaderPreset>,
 }
 
 impl Default for Settings {
     fn default() -> Self {
         Self {
             theme: "Classic".to_string(),
+            max_history: 100,
+            default_timeout_ms: 30_000,
+            follow_redirects: true,
+            verify_ssl: true,
+            show_line_numbers: false,
+            max_response_display_bytes: 5_000_000,
+            response_size_warning_bytes: 1024 * 1024,
+            layout_split_h: 30,
+            layout_split_v: 40,
+            cache_enabled: false,
+            auto_throttle: false,
+            auto_format_on_save: false,
+            auto_format_on_send: false,
+            show_minimap: false,
+            pretty_indent: 2,
+            auto_indent: true,
+            auto_validate_schema: false,
+            check_for_updates: false,
+            readonly_mode: false,
+            log_to_file: false,
+            log_file_path: None,
+            persistent_filter: None,
+            additional_env_files: Vec::new(),
+            jq_presets: JqPreset::defaults(),
+            header_presets: Vec::new(),
         }
     }
 }
@@ -25,6 +204,13 @@ impl Settings {
         Ok(settings)
     }
 
+    /// Debug log file: `log_file_path` if set, otherwise `default`
+    pub fn log_file(&self, default: &Path) -> PathBuf {
+        self.log_file_path
+            .clone()
+            .unwrap_or_else(|| default.to_path_buf())
+    }
+
     pub fn save(&self, path: &Path) -> Result<()> {
         let content = serde_json::to_string_pretty(self)?;
         std::fs::write(path, content)?;
@@ -0,0 +1,107 @@
+use crate::app::{App, BatchResultsPopup};
+use ratatui::{
+    layout::{Alignment, Constraint, Flex, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+pub fn draw_batch_results(frame: &mut Frame, app: &App, popup: &BatchResultsPopup) {
+    let accent = app.accent_color();
+    let theme = app.theme();
+
+    let value_width = popup
+        .results
+        .iter()
+        .map(|result| result.value.chars().count())
+        .max()
+        .unwrap_or(0)
+        .clamp(popup.param_name.chars().count().max(5), 40);
+
+    let mut lines = vec![Line::from(Span::styled(
+        format!(
+            "{:<width$}  {:<6}  {:>8}",
+            popup.param_name,
+            "Status",
+            "Time",
+            width = value_width
+        ),
+        Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD),
+    ))];
+    for result in popup.results.iter().skip(popup.scroll) {
+        let (status, status_color) = match (result.finished, result.status) {
+            (false, _) => (app.spinner_frame().to_string(), app.theme_muted_color()),
+            (true, Some(code)) if (200..300).contains(&code) => (code.to_string(), Color::Green),
+            (true, Some(code)) if code >= 400 => (code.to_string(), Color::Red),
+            (true, Some(code)) => (code.to_string(), Color::Yellow),
+            (true, None) => ("ERR".to_string(), Color::Red),
+        };
+        let duration = if result.finished && result.status.is_some() {
+            format!("{}ms", result.duration_ms)
+        } else {
+            String::new()
+        };
+
+        let value: String = result.value.chars().take(value_width).collect();
+        let mut spans = vec![
+            Span::styled(
+                format!("{:<width$}  ", value, width = value_width),
+                Style::default().fg(app.theme_text_color()),
+            ),
+            Span::styled(format!("{:<6}", status), Style::default().fg(status_color)),
+            Span::styled(
+                format!("  {:>8}", duration),
+                Style::default().fg(app.theme_muted_color()),
+            ),
+        ];
+        if let Some(error) = &result.error {
+            spans.push(Span::styled(
+                format!("  {}", error),
+                Style::default().fg(Color::Red),
+            ));
+        }
+        lines.push(Line::from(spans));
+    }
+
+    let popup_width = frame.area().width.saturating_sub(4).clamp(30, 80);
+    let popup_height = (lines.len() as u16 + 3).clamp(6, frame.area().height.max(6));
+    let area = centered_rect(popup_width, popup_height, frame.area());
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(format!(
+            " Batch Run ({}/{}) ",
+            popup.finished_count(),
+            popup.results.len()
+        ))
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(accent))
+        .style(Style::default().bg(theme.surface));
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+
+    let footer_area = Rect {
+        x: area.x,
+        y: area.y + area.height - 1,
+        width: area.width,
+        height: 1,
+    };
+    let footer = Paragraph::new(Line::from(vec![Span::styled(
+        " j/k scroll • Enter/Esc close ",
+        Style::default().fg(app.theme_muted_color()),
+    )]))
+    .alignment(Alignment::Center);
+    frame.render_widget(footer, footer_area);
+}
+
+fn centered_rect(width: u16, height: u16, area: Rect) -> Rect {
+    let horizontal = Layout::horizontal([Constraint::Length(width)]).flex(Flex::Center);
+    let vertical = Layout::vertical([Constraint::Length(height)]).flex(Flex::Center);
+
+    let [area] = vertical.areas(area);
+    let [area] = horizontal.areas(area);
+    area
+}
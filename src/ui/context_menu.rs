@@ -0,0 +1,71 @@
+use crate::app::App;
+use ratatui::{
+    layout::{Alignment, Constraint, Flex, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+pub fn draw_context_menu(frame: &mut Frame, app: &App) {
+    let accent = app.accent_color();
+    let theme = app.theme();
+    let menu = &app.context_menu;
+
+    let lines: Vec<Line> = menu
+        .items
+        .iter()
+        .enumerate()
+        .map(|(idx, item)| {
+            let style = if idx == menu.selected {
+                Style::default()
+                    .fg(app.theme_selection_fg())
+                    .bg(app.theme_selection_bg())
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(app.theme_text_color())
+            };
+            Line::from(Span::styled(format!(" {:<18}", item.label), style))
+        })
+        .collect();
+
+    // Open over the request list, where the selected item is
+    let bounds = app
+        .layout_areas
+        .request_list
+        .map(|(x, y, width, height)| Rect::new(x, y, width, height))
+        .filter(|rect| rect.width >= 22)
+        .unwrap_or_else(|| frame.area());
+    let area = centered_rect(22, lines.len() as u16 + 2, bounds);
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(" Actions ")
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(accent))
+        .style(Style::default().bg(theme.surface));
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+
+    let footer_area = Rect {
+        x: area.x,
+        y: area.y + area.height - 1,
+        width: area.width,
+        height: 1,
+    };
+    let footer = Paragraph::new(Line::from(vec![Span::styled(
+        " Enter run • Esc ",
+        Style::default().fg(app.theme_muted_color()),
+    )]))
+    .alignment(Alignment::Center);
+    frame.render_widget(footer, footer_area);
+}
+
+fn centered_rect(width: u16, height: u16, area: Rect) -> Rect {
+    let horizontal = Layout::horizontal([Constraint::Length(width)]).flex(Flex::Center);
+    let vertical = Layout::vertical([Constraint::Length(height)]).flex(Flex::Center);
+
+    let [area] = vertical.areas(area);
+    let [area] = horizontal.areas(area);
+    area
+}
@@ -0,0 +1,69 @@
+use crate::app::{App, CopyFormat};
+use ratatui::{
+    layout::{Alignment, Constraint, Flex, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+pub fn draw_copy_as(frame: &mut Frame, app: &App) {
+    let accent = app.accent_color();
+    let theme = app.theme();
+
+    let area = centered_rect(46, 5, frame.area());
+    frame.render_widget(Clear, area);
+
+    let mut spans = vec![Span::styled(
+        "Copy as: ",
+        Style::default().fg(app.theme_text_color()),
+    )];
+    for (idx, format) in CopyFormat::ALL.iter().enumerate() {
+        let style = if idx == app.copy_as_selected {
+            Style::default()
+                .fg(app.theme_selection_fg())
+                .bg(app.theme_selection_bg())
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(app.theme_muted_color())
+        };
+        if idx > 0 {
+            spans.push(Span::raw(" "));
+        }
+        spans.push(Span::styled(format!("[{}]", format.label()), style));
+    }
+
+    let block = Block::default()
+        .title(" Copy Request ")
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(accent))
+        .style(Style::default().bg(theme.surface));
+
+    let content = Paragraph::new(vec![Line::from(""), Line::from(spans)])
+        .block(block)
+        .alignment(Alignment::Center);
+    frame.render_widget(content, area);
+
+    let footer_area = Rect {
+        x: area.x,
+        y: area.y + area.height - 1,
+        width: area.width,
+        height: 1,
+    };
+    let footer = Paragraph::new(Line::from(vec![Span::styled(
+        " ←/→ select • Enter copy • Esc close ",
+        Style::default().fg(app.theme_muted_color()),
+    )]))
+    .alignment(Alignment::Center);
+    frame.render_widget(footer, footer_area);
+}
+
+fn centered_rect(width: u16, height: u16, area: Rect) -> Rect {
+    let horizontal = Layout::horizontal([Constraint::Length(width)]).flex(Flex::Center);
+    let vertical = Layout::vertical([Constraint::Length(height)]).flex(Flex::Center);
+
+    let [area] = vertical.areas(area);
+    let [area] = horizontal.areas(area);
+    area
+}
@@ -1,9 +1,11 @@
-use crate::app::{App, DialogType, ItemType};
+use crate::app::{
+    collection_colors, App, DialogType, ItemType, COLLECTION_COLOR_COLUMNS, HISTORY_CLEAR_RANGES,
+};
 use ratatui::{
     layout::{Alignment, Constraint, Flex, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Clear, Paragraph},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
     Frame,
 };
 
@@ -21,15 +23,55 @@ pub fn draw_dialog(frame: &mut Frame, app: &mut App) {
         DialogType::ConfirmDelete {
             item_type,
             item_name,
+            collection_index,
             ..
         } => {
-            draw_confirm_delete_dialog(frame, app, item_type, item_name, accent);
+            draw_confirm_delete_dialog(frame, app, item_type, item_name, *collection_index, accent);
             app.layout_areas.dialog_input_area = None;
         }
         DialogType::ConfirmOverwrite { path } => {
             draw_confirm_overwrite_dialog(frame, app, path, accent);
             app.layout_areas.dialog_input_area = None;
         }
+        DialogType::ConfirmLargeImport { path, size_bytes } => {
+            draw_confirm_large_import_dialog(frame, app, path, *size_bytes, accent);
+            app.layout_areas.dialog_input_area = None;
+        }
+        DialogType::ConfirmCloseSlot => {
+            draw_confirm_close_slot_dialog(frame, app, accent);
+            app.layout_areas.dialog_input_area = None;
+        }
+        DialogType::ConfirmQuit { message } => {
+            draw_confirm_quit_dialog(frame, app, message, accent);
+            app.layout_areas.dialog_input_area = None;
+        }
+        DialogType::ConfirmGraphQLBody => {
+            draw_confirm_graphql_body_dialog(frame, app, accent);
+            app.layout_areas.dialog_input_area = None;
+        }
+        DialogType::RestoreDraft => {
+            draw_restore_draft_dialog(frame, app, accent);
+            app.layout_areas.dialog_input_area = None;
+        }
+        DialogType::ConfirmReplaceBody => {
+            draw_confirm_replace_body_dialog(frame, app, accent);
+            app.layout_areas.dialog_input_area = None;
+        }
+        DialogType::SetCollectionColor {
+            collection_index,
+            selected,
+        } => {
+            draw_collection_color_dialog(frame, app, *collection_index, *selected, accent);
+            app.layout_areas.dialog_input_area = None;
+        }
+        DialogType::ClearHistory { selected } => {
+            draw_clear_history_dialog(frame, app, *selected, accent);
+            app.layout_areas.dialog_input_area = None;
+        }
+        _ if app.dialog.dialog_input_multiline => {
+            draw_multiline_input_dialog(frame, app, dialog_type, accent);
+            app.layout_areas.dialog_input_area = None;
+        }
         _ => {
             draw_input_dialog(frame, app, dialog_type);
         }
@@ -42,17 +84,54 @@ fn draw_input_dialog(frame: &mut Frame, app: &mut App, dialog_type: &DialogType)
         DialogType::CreateCollection => "New Collection",
         DialogType::CreateFolder { .. } => "New Folder",
         DialogType::CreateRequest { .. } => "New Request",
+        DialogType::QuickNewRequest { .. } if app.dialog.dialog_step == 0 => {
+            "Step 1/2: Request name"
+        }
+        DialogType::QuickNewRequest { .. } => "Step 2/2: HTTP method",
+        DialogType::ResponseTemplateMethod => "Response as Body: HTTP method",
+        DialogType::ImportFromCurl { .. } => "Import from curl: Request name",
         DialogType::RenameItem { item_type, .. } => match item_type {
             ItemType::Collection => "Rename Collection",
             ItemType::Folder => "Rename Folder",
             ItemType::Request => "Rename Request",
         },
         DialogType::SaveResponseAs => "Save Response As",
-        DialogType::ConfirmDelete { .. } | DialogType::ConfirmOverwrite { .. } => unreachable!(),
+        DialogType::ImportBodyFromFile => "Import Body From File",
+        DialogType::SaveHeaderPreset => "New Header Preset",
+        DialogType::SaveJqPreset => "New jq Preset",
+        DialogType::ExportCollectionHttp { .. } => "Export Collection as .http",
+        DialogType::AddEnvFile => "Merge Environment File",
+        DialogType::ExportMarkdown { .. } => "Export as Markdown",
+        DialogType::MergeCollection { .. } => "Merge Collection From File",
+        DialogType::BatchRun { .. } => "Batch Run",
+        DialogType::ConfirmDelete { .. }
+        | DialogType::ConfirmOverwrite { .. }
+        | DialogType::ConfirmLargeImport { .. }
+        | DialogType::ConfirmCloseSlot
+        | DialogType::ConfirmQuit { .. }
+        | DialogType::RestoreDraft
+        | DialogType::ConfirmGraphQLBody
+        | DialogType::ConfirmReplaceBody
+        | DialogType::SetCollectionColor { .. }
+        | DialogType::ClearHistory { .. } => unreachable!(),
+    };
+
+    let method_step = match dialog_type {
+        DialogType::QuickNewRequest { .. } => app.dialog.dialog_step == 1,
+        DialogType::ResponseTemplateMethod => true,
+        _ => false,
     };
 
     let prompt_label = match dialog_type {
-        DialogType::SaveResponseAs => "Path: ",
+        _ if method_step => "Method: ",
+        DialogType::SaveResponseAs
+        | DialogType::ImportBodyFromFile
+        | DialogType::ExportCollectionHttp { .. }
+        | DialogType::AddEnvFile
+        | DialogType::ExportMarkdown { .. }
+        | DialogType::MergeCollection { .. } => "Path: ",
+        DialogType::BatchRun { param_name, .. } if param_name.is_empty() => "Param: ",
+        DialogType::BatchRun { .. } => "Value: ",
         _ => "Name: ",
     };
     let prompt_label_len = prompt_label.chars().count() as u16;
@@ -87,15 +166,31 @@ fn draw_input_dialog(frame: &mut Frame, app: &mut App, dialog_type: &DialogType)
     // Input label and field with proper cursor
     let base_style = Style::default().fg(app.theme_text_color());
     let mut spans = vec![Span::styled(prompt_label, Style::default().fg(accent))];
-    let text_spans = text_with_cursor_and_selection(
-        &app.dialog.input_buffer,
-        app.dialog.cursor_position,
-        true, // always editing in dialog
-        "",
-        base_style,
-        selection,
-    );
-    spans.extend(text_spans);
+    if method_step {
+        let method = app.quick_new_request_method();
+        spans.push(Span::styled(
+            "◀ ",
+            Style::default().fg(app.theme_muted_color()),
+        ));
+        spans.push(Span::styled(
+            method.as_str(),
+            Style::default().fg(accent).add_modifier(Modifier::BOLD),
+        ));
+        spans.push(Span::styled(
+            " ▶",
+            Style::default().fg(app.theme_muted_color()),
+        ));
+    } else {
+        let text_spans = text_with_cursor_and_selection(
+            &app.dialog.input_buffer,
+            app.dialog.cursor_position,
+            true, // always editing in dialog
+            "",
+            base_style,
+            selection,
+        );
+        spans.extend(text_spans);
+    }
 
     let prompt = Paragraph::new(Line::from(spans));
 
@@ -110,16 +205,87 @@ fn draw_input_dialog(frame: &mut Frame, app: &mut App, dialog_type: &DialogType)
     // Store the input area for mouse handling (text starts after prompt label)
     let text_start_x = prompt_area.x + prompt_label_len;
     let text_width = prompt_area.width.saturating_sub(prompt_label_len);
-    app.layout_areas.dialog_input_area = Some((text_start_x, prompt_area.y, text_width));
+    app.layout_areas.dialog_input_area =
+        (!method_step).then_some((text_start_x, prompt_area.y, text_width));
+
+    // Quick new request: show the other field below the one being edited
+    if let DialogType::QuickNewRequest { .. } = dialog_type {
+        let (label, value) = if method_step {
+            ("Name: ", app.dialog.input_buffer.clone())
+        } else {
+            ("Method: ", app.dialog.dialog_secondary_buffer.clone())
+        };
+        let summary = Paragraph::new(Line::from(vec![
+            Span::styled(label, Style::default().fg(app.theme_muted_color())),
+            Span::styled(value, Style::default().fg(app.theme_muted_color())),
+        ]));
+        frame.render_widget(
+            summary,
+            Rect {
+                y: prompt_area.y + 1,
+                ..prompt_area
+            },
+        );
+    }
+
+    // Batch run: show the param and the values entered so far
+    if let DialogType::BatchRun { param_name, values } = dialog_type {
+        if !param_name.is_empty() {
+            let summary = Paragraph::new(Line::from(vec![
+                Span::styled(
+                    format!("{} = ", param_name),
+                    Style::default().fg(app.theme_muted_color()),
+                ),
+                Span::styled(values.join(", "), base_style),
+            ]));
+            frame.render_widget(
+                summary,
+                Rect {
+                    y: prompt_area.y + 1,
+                    ..prompt_area
+                },
+            );
+        }
+    }
 
     // Footer hints
-    let footer = Paragraph::new(Line::from(vec![
-        Span::styled("Enter", Style::default().fg(accent)),
-        Span::raw(": confirm  "),
-        Span::styled("Esc", Style::default().fg(accent)),
-        Span::raw(": cancel"),
-    ]))
-    .alignment(Alignment::Center);
+    let footer = match dialog_type {
+        DialogType::QuickNewRequest { .. } if method_step => Line::from(vec![
+            Span::styled("Tab", Style::default().fg(accent)),
+            Span::raw(": method  "),
+            Span::styled("Enter", Style::default().fg(accent)),
+            Span::raw(": create  "),
+            Span::styled("Esc", Style::default().fg(accent)),
+            Span::raw(": cancel"),
+        ]),
+        DialogType::ResponseTemplateMethod => Line::from(vec![
+            Span::styled("Tab", Style::default().fg(accent)),
+            Span::raw(": method  "),
+            Span::styled("Enter", Style::default().fg(accent)),
+            Span::raw(": use  "),
+            Span::styled("Esc", Style::default().fg(accent)),
+            Span::raw(": cancel"),
+        ]),
+        DialogType::QuickNewRequest { .. } => Line::from(vec![
+            Span::styled("Enter", Style::default().fg(accent)),
+            Span::raw(": next  "),
+            Span::styled("Esc", Style::default().fg(accent)),
+            Span::raw(": cancel"),
+        ]),
+        DialogType::BatchRun { param_name, .. } if !param_name.is_empty() => Line::from(vec![
+            Span::styled("Enter", Style::default().fg(accent)),
+            Span::raw(": add value (empty: run)  "),
+            Span::styled("Esc", Style::default().fg(accent)),
+            Span::raw(": cancel"),
+        ]),
+        _ => Line::from(vec![
+            Span::styled("Enter", Style::default().fg(accent)),
+            Span::raw(": confirm  "),
+            Span::styled("Esc", Style::default().fg(accent)),
+            Span::raw(": cancel"),
+        ]),
+    };
+    let footer = Paragraph::new(footer).alignment(Alignment::Center);
 
     let footer_area = Rect {
         x: inner.x,
@@ -130,11 +296,105 @@ fn draw_input_dialog(frame: &mut Frame, app: &mut App, dialog_type: &DialogType)
     frame.render_widget(footer, footer_area);
 }
 
+fn draw_multiline_input_dialog(
+    frame: &mut Frame,
+    app: &App,
+    dialog_type: &DialogType,
+    accent: Color,
+) {
+    let title = match dialog_type {
+        DialogType::ImportFromCurl { .. } => "Import from curl: Paste command",
+        _ => "Input",
+    };
+
+    let popup_width = 80;
+    let popup_height = 14;
+    let area = centered_rect(popup_width, popup_height, frame.area());
+
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(format!(" {} ", title))
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(accent))
+        .style(Style::default().bg(app.theme_surface_color()));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    // One row per line of input, the cursor drawn on the line that holds it
+    let base_style = Style::default().fg(app.theme_text_color());
+    let before_cursor: String = app
+        .dialog
+        .input_buffer
+        .chars()
+        .take(app.dialog.cursor_position)
+        .collect();
+    let cursor_line = before_cursor.matches('\n').count();
+    let cursor_column = before_cursor
+        .rsplit('\n')
+        .next()
+        .unwrap_or_default()
+        .chars()
+        .count();
+    let lines: Vec<Line> = app
+        .dialog
+        .input_buffer
+        .split('\n')
+        .enumerate()
+        .map(|(index, text)| {
+            if index == cursor_line {
+                Line::from(text_with_cursor_and_selection(
+                    text,
+                    cursor_column,
+                    true,
+                    "",
+                    base_style,
+                    None,
+                ))
+            } else {
+                Line::from(Span::styled(text.to_string(), base_style))
+            }
+        })
+        .collect();
+
+    let input_area = Rect {
+        x: inner.x + 1,
+        y: inner.y + 1,
+        width: inner.width.saturating_sub(2),
+        height: inner.height.saturating_sub(3),
+    };
+    // Keep the cursor line in view
+    let scroll = cursor_line.saturating_sub(input_area.height.saturating_sub(1) as usize);
+    frame.render_widget(Paragraph::new(lines).scroll((scroll as u16, 0)), input_area);
+
+    let footer = Paragraph::new(Line::from(vec![
+        Span::styled("Enter", Style::default().fg(accent)),
+        Span::raw(": next  "),
+        Span::styled("Shift+Enter", Style::default().fg(accent)),
+        Span::raw(": newline  "),
+        Span::styled("Esc", Style::default().fg(accent)),
+        Span::raw(": cancel"),
+    ]))
+    .alignment(Alignment::Center);
+    frame.render_widget(
+        footer,
+        Rect {
+            x: inner.x,
+            y: inner.y + inner.height - 1,
+            width: inner.width,
+            height: 1,
+        },
+    );
+}
+
 fn draw_confirm_delete_dialog(
     frame: &mut Frame,
     app: &App,
     item_type: &ItemType,
     item_name: &str,
+    collection_index: usize,
     accent: Color,
 ) {
     let type_str = match item_type {
@@ -160,7 +420,7 @@ fn draw_confirm_delete_dialog(
     frame.render_widget(block, area);
 
     // Warning message
-    let message = Paragraph::new(vec![
+    let mut lines = vec![
         Line::from(""),
         Line::from(Span::styled(
             format!("Delete this {}?", type_str),
@@ -171,8 +431,24 @@ fn draw_confirm_delete_dialog(
             format!("\"{}\"", item_name),
             Style::default().fg(accent).add_modifier(Modifier::BOLD),
         )),
-    ])
-    .alignment(Alignment::Center);
+    ];
+    if let (ItemType::Collection, Some(collection)) =
+        (item_type, app.collections.get(collection_index))
+    {
+        let requests = collection.count_requests();
+        let folders = collection.count_folders();
+        lines.push(Line::from(Span::styled(
+            format!(
+                "{} request{}, {} folder{}",
+                requests,
+                if requests == 1 { "" } else { "s" },
+                folders,
+                if folders == 1 { "" } else { "s" }
+            ),
+            Style::default().fg(app.theme_muted_color()),
+        )));
+    }
+    let message = Paragraph::new(lines).alignment(Alignment::Center);
 
     frame.render_widget(
         message,
@@ -281,6 +557,561 @@ fn draw_confirm_overwrite_dialog(frame: &mut Frame, app: &App, path: &std::path:
     );
 }
 
+fn draw_confirm_large_import_dialog(
+    frame: &mut Frame,
+    app: &App,
+    path: &std::path::Path,
+    size_bytes: u64,
+    accent: Color,
+) {
+    let filename = path.file_name().and_then(|s| s.to_str()).unwrap_or("file");
+
+    let popup_width = 55;
+    let popup_height = 10;
+    let area = centered_rect(popup_width, popup_height, frame.area());
+
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(" Large File ")
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Yellow))
+        .style(Style::default().bg(app.theme_surface_color()));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    // Warning message
+    let message = Paragraph::new(vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            format!("\"{}\"", filename),
+            Style::default().fg(accent).add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+        Line::from(Span::styled(
+            format!(
+                "File is {:.1} MB. Load it into the body anyway?",
+                size_bytes as f64 / (1024.0 * 1024.0)
+            ),
+            Style::default().fg(app.theme_text_color()),
+        )),
+    ])
+    .alignment(Alignment::Center);
+
+    frame.render_widget(
+        message,
+        Rect {
+            x: inner.x,
+            y: inner.y,
+            width: inner.width,
+            height: 5,
+        },
+    );
+
+    // Footer hints
+    let footer = Paragraph::new(Line::from(vec![
+        Span::styled(
+            "y",
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(": load  "),
+        Span::styled("n/Esc", Style::default().fg(accent)),
+        Span::raw(": cancel"),
+    ]))
+    .alignment(Alignment::Center);
+
+    frame.render_widget(
+        footer,
+        Rect {
+            x: inner.x,
+            y: inner.y + inner.height - 1,
+            width: inner.width,
+            height: 1,
+        },
+    );
+}
+
+fn draw_confirm_quit_dialog(frame: &mut Frame, app: &App, message: &str, accent: Color) {
+    let popup_width = 50;
+    let popup_height = 8;
+    let area = centered_rect(popup_width, popup_height, frame.area());
+
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(" Quit ")
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Yellow))
+        .style(Style::default().bg(app.theme_surface_color()));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let message = Paragraph::new(vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            message.to_string(),
+            Style::default().fg(app.theme_text_color()),
+        )),
+    ])
+    .alignment(Alignment::Center)
+    .wrap(Wrap { trim: true });
+
+    frame.render_widget(
+        message,
+        Rect {
+            x: inner.x,
+            y: inner.y,
+            width: inner.width,
+            height: 4,
+        },
+    );
+
+    let footer = Paragraph::new(Line::from(vec![
+        Span::styled(
+            "y",
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(": quit  "),
+        Span::styled("s", Style::default().fg(accent)),
+        Span::raw(": save & quit  "),
+        Span::styled("n/Esc", Style::default().fg(accent)),
+        Span::raw(": cancel"),
+    ]))
+    .alignment(Alignment::Center);
+
+    frame.render_widget(
+        footer,
+        Rect {
+            x: inner.x,
+            y: inner.y + inner.height - 1,
+            width: inner.width,
+            height: 1,
+        },
+    );
+}
+
+fn draw_confirm_close_slot_dialog(frame: &mut Frame, app: &App, accent: Color) {
+    let popup_width = 50;
+    let popup_height = 8;
+    let area = centered_rect(popup_width, popup_height, frame.area());
+
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(" Close Slot ")
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Yellow))
+        .style(Style::default().bg(app.theme_surface_color()));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let message = Paragraph::new(vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            format!("Slot {} has unsaved changes.", app.active_slot + 1),
+            Style::default().fg(app.theme_text_color()),
+        )),
+        Line::from(Span::styled(
+            "Close it anyway?",
+            Style::default().fg(app.theme_text_color()),
+        )),
+    ])
+    .alignment(Alignment::Center);
+
+    frame.render_widget(
+        message,
+        Rect {
+            x: inner.x,
+            y: inner.y,
+            width: inner.width,
+            height: 4,
+        },
+    );
+
+    let footer = Paragraph::new(Line::from(vec![
+        Span::styled(
+            "y",
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(": close  "),
+        Span::styled("n/Esc", Style::default().fg(accent)),
+        Span::raw(": cancel"),
+    ]))
+    .alignment(Alignment::Center);
+
+    frame.render_widget(
+        footer,
+        Rect {
+            x: inner.x,
+            y: inner.y + inner.height - 1,
+            width: inner.width,
+            height: 1,
+        },
+    );
+}
+
+fn draw_confirm_graphql_body_dialog(frame: &mut Frame, app: &App, accent: Color) {
+    let popup_width = 50;
+    let popup_height = 7;
+    let area = centered_rect(popup_width, popup_height, frame.area());
+
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(" Body Type ")
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(accent))
+        .style(Style::default().bg(app.theme_surface_color()));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let message = Paragraph::new(vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            "Looks like GraphQL — switch body type?",
+            Style::default().fg(app.theme_text_color()),
+        )),
+    ])
+    .alignment(Alignment::Center);
+
+    frame.render_widget(
+        message,
+        Rect {
+            x: inner.x,
+            y: inner.y,
+            width: inner.width,
+            height: 3,
+        },
+    );
+
+    let footer = Paragraph::new(Line::from(vec![
+        Span::styled(
+            "y",
+            Style::default().fg(accent).add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(": switch  "),
+        Span::styled("n/Esc", Style::default().fg(accent)),
+        Span::raw(": keep"),
+    ]))
+    .alignment(Alignment::Center);
+
+    frame.render_widget(
+        footer,
+        Rect {
+            x: inner.x,
+            y: inner.y + inner.height - 1,
+            width: inner.width,
+            height: 1,
+        },
+    );
+}
+
+fn draw_restore_draft_dialog(frame: &mut Frame, app: &App, accent: Color) {
+    let popup_width = 50;
+    let popup_height = 8;
+    let area = centered_rect(popup_width, popup_height, frame.area());
+
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(" Unsaved Draft ")
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Yellow))
+        .style(Style::default().bg(app.theme_surface_color()));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let name = app.pending_draft_name().unwrap_or_default().to_string();
+    let message = Paragraph::new(vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            "Restore unsaved draft?",
+            Style::default().fg(app.theme_text_color()),
+        )),
+        Line::from(Span::styled(
+            format!("\"{}\"", name),
+            Style::default().fg(accent).add_modifier(Modifier::BOLD),
+        )),
+    ])
+    .alignment(Alignment::Center);
+
+    frame.render_widget(
+        message,
+        Rect {
+            x: inner.x,
+            y: inner.y,
+            width: inner.width,
+            height: 4,
+        },
+    );
+
+    let footer = Paragraph::new(Line::from(vec![
+        Span::styled(
+            "y",
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(": restore  "),
+        Span::styled("n/Esc", Style::default().fg(accent)),
+        Span::raw(": discard"),
+    ]))
+    .alignment(Alignment::Center);
+
+    frame.render_widget(
+        footer,
+        Rect {
+            x: inner.x,
+            y: inner.y + inner.height - 1,
+            width: inner.width,
+            height: 1,
+        },
+    );
+}
+
+fn draw_confirm_replace_body_dialog(frame: &mut Frame, app: &App, accent: Color) {
+    let popup_width = 50;
+    let popup_height = 7;
+    let area = centered_rect(popup_width, popup_height, frame.area());
+
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(" Replace Body ")
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(accent))
+        .style(Style::default().bg(app.theme_surface_color()));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let message = Paragraph::new(vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            "Replace the body with the filtered response?",
+            Style::default().fg(app.theme_text_color()),
+        )),
+    ])
+    .alignment(Alignment::Center);
+
+    frame.render_widget(
+        message,
+        Rect {
+            x: inner.x,
+            y: inner.y,
+            width: inner.width,
+            height: 3,
+        },
+    );
+
+    let footer = Paragraph::new(Line::from(vec![
+        Span::styled(
+            "y",
+            Style::default().fg(accent).add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(": replace  "),
+        Span::styled("n/Esc", Style::default().fg(accent)),
+        Span::raw(": cancel"),
+    ]))
+    .alignment(Alignment::Center);
+
+    frame.render_widget(
+        footer,
+        Rect {
+            x: inner.x,
+            y: inner.y + inner.height - 1,
+            width: inner.width,
+            height: 1,
+        },
+    );
+}
+
+fn draw_collection_color_dialog(
+    frame: &mut Frame,
+    app: &App,
+    collection_index: usize,
+    selected: usize,
+    accent: Color,
+) {
+    let popup_width = 40;
+    let popup_height = 9;
+    let area = centered_rect(popup_width, popup_height, frame.area());
+
+    frame.render_widget(Clear, area);
+
+    let name = app
+        .collections
+        .get(collection_index)
+        .map(|collection| collection.name.as_str())
+        .unwrap_or_default();
+    let block = Block::default()
+        .title(format!(" Color: {} ", name))
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(accent))
+        .style(Style::default().bg(app.theme_surface_color()));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let colors = collection_colors();
+    let mut lines = vec![Line::from("")];
+    for (row, chunk) in colors.chunks(COLLECTION_COLOR_COLUMNS).enumerate() {
+        let mut spans = Vec::new();
+        for (col, color) in chunk.iter().enumerate() {
+            let is_selected = row * COLLECTION_COLOR_COLUMNS + col == selected;
+            let (left, right) = if is_selected { ("[", "]") } else { (" ", " ") };
+            spans.push(Span::styled(left, Style::default().fg(accent)));
+            match color {
+                Some(color) => spans.push(Span::styled(
+                    "███",
+                    Style::default().fg(App::parse_color_pub(color)),
+                )),
+                None => spans.push(Span::styled(
+                    " ∅ ",
+                    Style::default().fg(app.theme_muted_color()),
+                )),
+            }
+            spans.push(Span::styled(right, Style::default().fg(accent)));
+        }
+        lines.push(Line::from(spans));
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        colors
+            .get(selected)
+            .cloned()
+            .flatten()
+            .unwrap_or_else(|| "none".to_string()),
+        Style::default().fg(app.theme_text_color()),
+    )));
+
+    frame.render_widget(
+        Paragraph::new(lines).alignment(Alignment::Center),
+        Rect {
+            x: inner.x,
+            y: inner.y,
+            width: inner.width,
+            height: inner.height.saturating_sub(1),
+        },
+    );
+
+    let footer = Paragraph::new(Line::from(vec![
+        Span::styled("←↑↓→", Style::default().fg(accent)),
+        Span::raw(": select  "),
+        Span::styled(
+            "Enter",
+            Style::default().fg(accent).add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(": apply  "),
+        Span::styled("Esc", Style::default().fg(accent)),
+        Span::raw(": cancel"),
+    ]))
+    .alignment(Alignment::Center);
+
+    frame.render_widget(
+        footer,
+        Rect {
+            x: inner.x,
+            y: inner.y + inner.height - 1,
+            width: inner.width,
+            height: 1,
+        },
+    );
+}
+
+fn draw_clear_history_dialog(frame: &mut Frame, app: &App, selected: usize, accent: Color) {
+    let popup_width = 50;
+    let popup_height = 7;
+    let area = centered_rect(popup_width, popup_height, frame.area());
+
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(" Clear History ")
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(accent))
+        .style(Style::default().bg(app.theme_surface_color()));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let mut options = Vec::new();
+    for (idx, (label, _)) in HISTORY_CLEAR_RANGES.iter().enumerate() {
+        let style = if idx == selected {
+            Style::default()
+                .fg(app.theme_surface_color())
+                .bg(accent)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(app.theme_text_color())
+        };
+        options.push(Span::styled(format!("[{}]", label), style));
+        options.push(Span::raw(" "));
+    }
+
+    let message = Paragraph::new(vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            "Clear older than:",
+            Style::default().fg(app.theme_text_color()),
+        )),
+        Line::from(options),
+    ])
+    .alignment(Alignment::Center);
+
+    frame.render_widget(
+        message,
+        Rect {
+            x: inner.x,
+            y: inner.y,
+            width: inner.width,
+            height: 3,
+        },
+    );
+
+    let footer = Paragraph::new(Line::from(vec![
+        Span::styled("←/→", Style::default().fg(accent)),
+        Span::raw(": select  "),
+        Span::styled(
+            "Enter",
+            Style::default().fg(accent).add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(": clear  "),
+        Span::styled("Esc", Style::default().fg(accent)),
+        Span::raw(": cancel"),
+    ]))
+    .alignment(Alignment::Center);
+
+    frame.render_widget(
+        footer,
+        Rect {
+            x: inner.x,
+            y: inner.y + inner.height - 1,
+            width: inner.width,
+            height: 1,
+        },
+    );
+}
+
 fn centered_rect(width: u16, height: u16, area: Rect) -> Rect {
     let horizontal = Layout::horizontal([Constraint::Length(width)]).flex(Flex::Center);
     let vertical = Layout::vertical([Constraint::Length(height)]).flex(Flex::Center);
@@ -0,0 +1,72 @@
+use crate::app::App;
+use crate::http::ResponseEncoding;
+use ratatui::{
+    layout::{Alignment, Constraint, Flex, Layout, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+pub fn draw_encoding_popup(frame: &mut Frame, app: &App) {
+    let accent = app.accent_color();
+    let theme = app.theme();
+
+    let area = centered_rect(32, ResponseEncoding::ALL.len() as u16 + 4, frame.area());
+    frame.render_widget(Clear, area);
+
+    let lines: Vec<Line> = ResponseEncoding::ALL
+        .iter()
+        .enumerate()
+        .map(|(idx, encoding)| {
+            let is_current = app.response_encoding == Some(*encoding);
+            let style = if idx == app.encoding_popup_selected {
+                Style::default()
+                    .fg(app.theme_selection_fg())
+                    .bg(app.theme_selection_bg())
+            } else {
+                Style::default().fg(app.theme_text_color())
+            };
+            Line::from(Span::styled(
+                format!(
+                    " {} {} ",
+                    if is_current { "●" } else { " " },
+                    encoding.label()
+                ),
+                style,
+            ))
+        })
+        .collect();
+
+    let block = Block::default()
+        .title(" Response Encoding ")
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(accent))
+        .style(Style::default().bg(theme.surface));
+
+    let content = Paragraph::new(lines).block(block);
+    frame.render_widget(content, area);
+
+    let footer_area = Rect {
+        x: area.x,
+        y: area.y + area.height - 1,
+        width: area.width,
+        height: 1,
+    };
+    let footer = Paragraph::new(Line::from(vec![Span::styled(
+        " Enter decode • Esc close ",
+        Style::default().fg(app.theme_muted_color()),
+    )]))
+    .alignment(Alignment::Center);
+    frame.render_widget(footer, footer_area);
+}
+
+fn centered_rect(width: u16, height: u16, area: Rect) -> Rect {
+    let horizontal = Layout::horizontal([Constraint::Length(width)]).flex(Flex::Center);
+    let vertical = Layout::vertical([Constraint::Length(height)]).flex(Flex::Center);
+
+    let [area] = vertical.areas(area);
+    let [area] = horizontal.areas(area);
+    area
+}
@@ -0,0 +1,129 @@
+use crate::app::{App, EnvDiffPopup};
+use ratatui::{
+    layout::{Alignment, Constraint, Flex, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+pub fn draw_env_diff(frame: &mut Frame, app: &App, popup: &EnvDiffPopup) {
+    let accent = app.accent_color();
+    let theme = app.theme();
+    let diff = &popup.diff;
+    let muted = Style::default().fg(app.theme_muted_color());
+    let mask = |key: &str, value: &str| {
+        if app.environments.is_qualified_secret(key) || popup.incoming.is_qualified_secret(key) {
+            "••••••••".to_string()
+        } else {
+            value.to_string()
+        }
+    };
+
+    // One entry per change, in the same order as `popup.accepted`
+    let mut rows: Vec<(char, Color, &str, Vec<Span>)> = Vec::new();
+    for key in &diff.added {
+        let value = popup.incoming.qualified_value(key).unwrap_or_default();
+        rows.push((
+            '+',
+            Color::Green,
+            key,
+            vec![Span::styled(format!(" = {}", mask(key, value)), muted)],
+        ));
+    }
+    for key in &diff.removed {
+        rows.push(('-', Color::Red, key, Vec::new()));
+    }
+    for (key, old, new) in &diff.changed {
+        rows.push((
+            '~',
+            Color::Yellow,
+            key,
+            vec![
+                Span::styled(format!(": {}", mask(key, old)), muted),
+                Span::styled(" → ", muted),
+                Span::styled(mask(key, new), Style::default().fg(app.theme_text_color())),
+            ],
+        ));
+    }
+
+    let popup_width = frame.area().width.saturating_sub(4).clamp(30, 90);
+    let popup_height = (rows.len() as u16 + 5).clamp(8, frame.area().height.max(8));
+    let area = centered_rect(popup_width, popup_height, frame.area());
+    frame.render_widget(Clear, area);
+
+    // Keep the selected row in view
+    let visible = popup_height.saturating_sub(4) as usize;
+    let offset = popup.selected.saturating_sub(visible.saturating_sub(1));
+
+    let heading = Style::default()
+        .fg(Color::Yellow)
+        .add_modifier(Modifier::BOLD);
+    let mut lines = vec![Line::from(Span::styled(
+        format!(
+            "{} added, {} removed, {} changed",
+            diff.added.len(),
+            diff.removed.len(),
+            diff.changed.len()
+        ),
+        heading,
+    ))];
+    lines.push(Line::from(""));
+    for (index, (marker, color, key, detail)) in
+        rows.into_iter().enumerate().skip(offset).take(visible)
+    {
+        let accepted = popup.accepted.get(index).copied().unwrap_or(false);
+        let checkbox = if accepted { "[x] " } else { "[ ] " };
+        let key_style = if accepted {
+            Style::default().fg(app.theme_text_color())
+        } else {
+            muted.add_modifier(Modifier::CROSSED_OUT)
+        };
+
+        let mut spans = vec![
+            Span::styled(checkbox, muted),
+            Span::styled(format!("{} ", marker), Style::default().fg(color)),
+            Span::styled(key.to_string(), key_style),
+        ];
+        spans.extend(detail);
+        let mut line = Line::from(spans);
+        if index == popup.selected {
+            line = line.style(
+                Style::default()
+                    .bg(app.theme_selection_bg())
+                    .fg(app.theme_selection_fg()),
+            );
+        }
+        lines.push(line);
+    }
+
+    let block = Block::default()
+        .title(" Reload Environments ")
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(accent))
+        .style(Style::default().bg(theme.surface));
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+
+    let footer_area = Rect {
+        x: area.x,
+        y: area.y + area.height - 1,
+        width: area.width,
+        height: 1,
+    };
+    let footer = Paragraph::new(Line::from(vec![Span::styled(
+        " Space accept/reject • Enter apply • Esc cancel ",
+        Style::default().fg(app.theme_muted_color()),
+    )]))
+    .alignment(Alignment::Center);
+    frame.render_widget(footer, footer_area);
+}
+
+fn centered_rect(width: u16, height: u16, area: Rect) -> Rect {
+    let horizontal = Layout::horizontal([Constraint::Length(width)]).flex(Flex::Center);
+    let vertical = Layout::vertical([Constraint::Length(height)]).flex(Flex::Center);
+
+    let [area] = vertical.areas(area);
+    let [area] = horizontal.areas(area);
+    area
+}
@@ -13,20 +13,44 @@ pub fn draw_env_popup(frame: &mut Frame, app: &mut App) {
     let accent = app.accent_color();
     let active_name = app.environments.active_name();
 
-    let sections = [
-        EnvSection {
-            title: "Shared".to_string(),
-            placeholder: "No shared variables. Press 'a' or Enter to add.",
+    let collection_name = app
+        .env_popup
+        .collection
+        .and_then(|index| app.collections.get(index))
+        .map(|collection| collection.name.clone());
+
+    let sections = match &collection_name {
+        // Collection variables are held in the shared section
+        Some(name) => vec![EnvSection {
+            title: format!("Collection: {}", name),
+            placeholder: "No collection variables. Press 'a' or Enter to add.",
             items: &app.env_popup.shared,
+            secrets: &app.env_popup.shared_secrets,
             section: EnvPopupSection::Shared,
-        },
-        EnvSection {
-            title: format!("Env: {}", active_name),
-            placeholder: "No env variables. Press 'a' or Enter to add.",
-            items: &app.env_popup.active,
-            section: EnvPopupSection::Active,
-        },
-    ];
+        }],
+        None => vec![
+            EnvSection {
+                title: "Shared".to_string(),
+                placeholder: "No shared variables. Press 'a' or Enter to add.",
+                items: &app.env_popup.shared,
+                secrets: &app.env_popup.shared_secrets,
+                section: EnvPopupSection::Shared,
+            },
+            EnvSection {
+                title: format!("Env: {}", active_name),
+                placeholder: "No env variables. Press 'a' or Enter to add.",
+                items: &app.env_popup.active,
+                secrets: &app.env_popup.active_secrets,
+                section: EnvPopupSection::Active,
+            },
+        ],
+    };
+
+    let merged = if collection_name.is_some() {
+        Vec::new()
+    } else {
+        app.environments.additional_variables()
+    };
 
     let mut max_key_len = 0usize;
     let mut max_val_len = 0usize;
@@ -36,6 +60,10 @@ pub fn draw_env_popup(frame: &mut Frame, app: &mut App) {
             max_val_len = max_val_len.max(item.value.len());
         }
     }
+    for (key, value, file) in &merged {
+        max_key_len = max_key_len.max(key.len());
+        max_val_len = max_val_len.max(value.len() + file.label().len() + 3);
+    }
 
     let mut lines: Vec<Line> = Vec::new();
     let key_width = max_key_len.max(8);
@@ -105,25 +133,75 @@ pub fn draw_env_popup(frame: &mut Frame, app: &mut App) {
                     selection,
                 ));
                 spans.push(Span::raw(" = "));
-                let selection = if is_editing_value {
-                    app.get_selection_range()
+                let is_secret = section.secrets.iter().any(|key| *key == item.key.trim());
+                if is_secret && !is_editing_value {
+                    spans.push(Span::styled(
+                        "••••••••",
+                        Style::default().fg(app.theme_muted_color()),
+                    ));
                 } else {
-                    None
-                };
-                spans.extend(text_with_cursor_and_selection(
-                    &item.value,
-                    app.cursor_position,
-                    is_editing_value,
-                    "value",
-                    Style::default().fg(app.theme_text_color()),
-                    selection,
-                ));
+                    let selection = if is_editing_value {
+                        app.get_selection_range()
+                    } else {
+                        None
+                    };
+                    spans.extend(text_with_cursor_and_selection(
+                        &item.value,
+                        app.cursor_position,
+                        is_editing_value,
+                        "value",
+                        Style::default().fg(app.theme_text_color()),
+                        selection,
+                    ));
+                }
 
                 lines.push(Line::from(spans));
             }
         }
     }
 
+    if !merged.is_empty() {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            "-- From additional files --",
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )));
+        for (key, value, file) in &merged {
+            // Variables defined in the primary file win over merged ones
+            let overridden = sections
+                .iter()
+                .any(|section| section.items.iter().any(|item| item.key.trim() == key));
+            let (key_style, value_style) = if overridden {
+                let muted = Style::default().fg(app.theme_muted_color());
+                (muted, muted.add_modifier(Modifier::CROSSED_OUT))
+            } else {
+                (
+                    Style::default().fg(accent),
+                    Style::default().fg(app.theme_text_color()),
+                )
+            };
+            let value = if app.environments.is_additional_secret(file, key) {
+                "••••••••".to_string()
+            } else {
+                value.clone()
+            };
+            let source = if overridden {
+                format!("  ← {} (overridden)", file.label())
+            } else {
+                format!("  ← {}", file.label())
+            };
+            lines.push(Line::from(vec![
+                Span::raw("  "),
+                Span::styled(key.clone(), key_style),
+                Span::raw(" = "),
+                Span::styled(value, value_style),
+                Span::styled(source, Style::default().fg(app.theme_muted_color())),
+            ]));
+        }
+    }
+
     let popup_height = (lines.len() + 6).min(40).max(10) as u16;
     let visible_height = popup_height.saturating_sub(3) as usize;
     let max_scroll = lines.len().saturating_sub(visible_height) as u16;
@@ -134,8 +212,18 @@ pub fn draw_env_popup(frame: &mut Frame, app: &mut App) {
     let area = centered_rect(popup_width, popup_height, frame.area());
     frame.render_widget(Clear, area);
 
+    let title = if collection_name.is_some() {
+        " Collection Variables ".to_string()
+    } else if app.environments.additional.is_empty() {
+        " Env Variables ".to_string()
+    } else {
+        format!(
+            " Env Variables [merged from {} files] ",
+            app.environments.additional.len() + 1
+        )
+    };
     let env_block = Block::default()
-        .title(" Env Variables ")
+        .title(title)
         .title_alignment(Alignment::Center)
         .borders(Borders::ALL)
         .border_style(Style::default().fg(accent))
@@ -153,19 +241,109 @@ pub fn draw_env_popup(frame: &mut Frame, app: &mut App) {
         width: area.width,
         height: 1,
     };
-    let footer_text = " Enter edit • Tab next • a add • x delete • Esc close ";
+    let footer_text = if collection_name.is_some() {
+        " Enter edit • Tab next • a add • x delete • ^F find • Esc close "
+    } else {
+        " Enter edit • Tab next • a add • x delete • m mask • A merge file • ^F find • Esc close "
+    };
     let footer = Paragraph::new(Line::from(vec![Span::styled(
         truncate_with_ellipsis(footer_text, content_width),
         Style::default().fg(app.theme_muted_color()),
     )]))
     .alignment(Alignment::Center);
     frame.render_widget(footer, footer_area);
+
+    if app.env_quick_find.is_some() {
+        draw_env_quick_find(frame, app);
+    }
+}
+
+/// Ctrl+F finder over the popup's variable keys, drawn on top of it
+fn draw_env_quick_find(frame: &mut Frame, app: &App) {
+    let Some(find) = &app.env_quick_find else {
+        return;
+    };
+    let accent = app.accent_color();
+    let muted = Style::default().fg(app.theme_muted_color());
+    let results = app.env_quick_find_results();
+
+    let popup_width = frame.area().width.saturating_sub(4).clamp(30, 60);
+    let list_height = results.len().clamp(1, 10);
+    let popup_height = (list_height + 4) as u16;
+    let content_width = popup_width.saturating_sub(4) as usize;
+
+    let mut lines = vec![
+        Line::from(vec![
+            Span::styled("Find: ", Style::default().fg(accent)),
+            Span::styled(
+                format!("{}█", find.query),
+                Style::default().fg(app.theme_text_color()),
+            ),
+        ]),
+        Line::from(""),
+    ];
+
+    if results.is_empty() {
+        lines.push(Line::from(Span::styled("No matching variables", muted)));
+    }
+    // Keep the selected result in view
+    let offset = (find.selected + 1).saturating_sub(list_height);
+    for (row, &(section, index)) in results.iter().enumerate().skip(offset).take(list_height) {
+        let (items, secrets) = match section {
+            EnvPopupSection::Shared => (&app.env_popup.shared, &app.env_popup.shared_secrets),
+            EnvPopupSection::Active => (&app.env_popup.active, &app.env_popup.active_secrets),
+        };
+        let Some(item) = items.get(index) else {
+            continue;
+        };
+        let key = item.key.trim();
+        let selected = row == find.selected;
+        let key_style = if selected {
+            Style::default().fg(accent).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(app.theme_text_color())
+        };
+        let value = if secrets.iter().any(|secret| secret == key) {
+            "••••••••".to_string()
+        } else {
+            item.value.clone()
+        };
+        let prefix = if selected { "> " } else { "  " };
+        let hint_width = content_width.saturating_sub(prefix.len() + key.chars().count() + 3);
+        lines.push(Line::from(vec![
+            Span::styled(prefix, Style::default().fg(accent)),
+            Span::styled(key.to_string(), key_style),
+            Span::styled(" = ", muted),
+            Span::styled(truncate_with_ellipsis(&value, hint_width), muted),
+        ]));
+    }
+
+    let area = centered_rect(popup_width, popup_height, frame.area());
+    frame.render_widget(Clear, area);
+    let block = Block::default()
+        .title(" Find Variable ")
+        .title_alignment(Alignment::Center)
+        .title_bottom(Line::from(" ↑↓ select • Enter jump • Esc back ").centered())
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(accent))
+        .style(Style::default().bg(app.theme_surface_color()));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+    frame.render_widget(
+        Paragraph::new(lines),
+        Rect {
+            x: inner.x + 1,
+            width: inner.width.saturating_sub(2),
+            ..inner
+        },
+    );
 }
 
 struct EnvSection<'a> {
     title: String,
     placeholder: &'a str,
     items: &'a Vec<KeyValue>,
+    secrets: &'a Vec<String>,
     section: EnvPopupSection,
 }
 
@@ -1,3 +1,4 @@
+use super::request_list::highlight_matches;
 use crate::app::App;
 use ratatui::{
     layout::{Alignment, Constraint, Flex, Layout, Rect},
@@ -19,12 +20,21 @@ pub fn draw_filter_history(frame: &mut Frame, app: &App) {
         .unwrap_or(10);
 
     let popup_width = (max_filter_len + 8).min(60).max(30) as u16;
-    let popup_height = (app.filter_history.len() + 4).min(15).max(7) as u16;
+    let show_search =
+        app.filter_history_search_active || !app.filter_history_search_query.is_empty();
+    let popup_height = (app.filter_history.len() + 4 + usize::from(show_search))
+        .min(15)
+        .max(7) as u16;
     let area = centered_rect(popup_width, popup_height, frame.area());
     frame.render_widget(Clear, area);
 
     let mut lines = Vec::new();
-    for (idx, filter) in app.filter_history.iter().enumerate() {
+    let filtered = app.filtered_filter_history_indices();
+    for (idx, filter) in filtered
+        .iter()
+        .filter_map(|&idx| app.filter_history.get(idx))
+        .enumerate()
+    {
         let is_selected = idx == app.filter_history_selected;
         let line_style = if is_selected {
             Style::default()
@@ -41,19 +51,46 @@ pub fn draw_filter_history(frame: &mut Frame, app: &App) {
             filter.clone()
         };
 
-        lines.push(Line::from(vec![Span::styled(
-            format!(" {} ", display_filter),
+        let mut spans = vec![Span::styled(" ", line_style)];
+        spans.extend(highlight_matches(
+            &display_filter,
+            &app.filter_history_search_query,
             line_style,
-        )]));
+            accent,
+        ));
+        spans.push(Span::styled(" ", line_style));
+        lines.push(Line::from(spans));
     }
 
     if lines.is_empty() {
+        let message = if app.filter_history.is_empty() {
+            "No filter history"
+        } else {
+            "No matches"
+        };
         lines.push(Line::from(Span::styled(
-            "No filter history",
+            message,
             Style::default().fg(app.theme_muted_color()),
         )));
     }
 
+    if show_search {
+        let cursor = if app.filter_history_search_active {
+            "█"
+        } else {
+            ""
+        };
+        // Keep the search line just above the footer
+        let visible_rows = popup_height.saturating_sub(3) as usize;
+        while lines.len() < visible_rows {
+            lines.push(Line::from(""));
+        }
+        lines.push(Line::from(Span::styled(
+            format!(" / {}{}", app.filter_history_search_query, cursor),
+            Style::default().fg(accent),
+        )));
+    }
+
     let block = Block::default()
         .title(" Filter History ")
         .title_alignment(Alignment::Center)
@@ -73,7 +110,11 @@ pub fn draw_filter_history(frame: &mut Frame, app: &App) {
         height: 1,
     };
     let footer = Paragraph::new(Line::from(vec![Span::styled(
-        " Enter apply • d delete • Esc close ",
+        if app.filter_history_search_active {
+            " Enter done • Esc clear "
+        } else {
+            " Enter apply • / search • d delete • Esc close "
+        },
         Style::default().fg(app.theme_muted_color()),
     )]))
     .alignment(Alignment::Center);
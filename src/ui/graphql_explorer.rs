@@ -0,0 +1,230 @@
+use crate::app::{App, GraphQLExplorerPane};
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Flex, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, Wrap},
+    Frame,
+};
+
+pub fn draw_graphql_explorer(frame: &mut Frame, app: &App) {
+    let accent = app.accent_color();
+    let theme = app.theme();
+    let Some(schema) = app.graphql_schema() else {
+        return;
+    };
+
+    let popup_width = (frame.area().width * 4 / 5).max(40);
+    let popup_height = (frame.area().height * 4 / 5).max(12);
+    let area = centered_rect(popup_width, popup_height, frame.area());
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(format!(" GraphQL Schema ({}) ", schema.url))
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(accent))
+        .style(Style::default().bg(theme.surface));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(1), Constraint::Length(1)])
+        .split(inner);
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(35), Constraint::Percentage(65)])
+        .split(rows[0]);
+
+    draw_types(frame, app, columns[0]);
+    draw_fields(frame, app, columns[1]);
+
+    let footer_text = if app.graphql_explorer.filter_active {
+        " Enter done • Esc clear "
+    } else {
+        " / filter • ←/→ switch list • Enter insert field • r refetch • Esc close "
+    };
+    let footer = Paragraph::new(Line::from(Span::styled(
+        footer_text,
+        Style::default().fg(app.theme_muted_color()),
+    )))
+    .alignment(Alignment::Center);
+    frame.render_widget(footer, rows[1]);
+}
+
+fn draw_types(frame: &mut Frame, app: &App, area: Rect) {
+    let explorer = &app.graphql_explorer;
+    let focused = explorer.pane == GraphQLExplorerPane::Types;
+    let types = app.graphql_filtered_types();
+    let root_types = app
+        .graphql_schema()
+        .map(|schema| [schema.query_type.clone(), schema.mutation_type.clone()])
+        .unwrap_or_default();
+
+    let block = pane_block(app, "Types", focused);
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let show_filter = explorer.filter_active || !explorer.filter.is_empty();
+    let (list_area, filter_area) = if show_filter {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(1), Constraint::Length(1)])
+            .split(inner);
+        (chunks[0], Some(chunks[1]))
+    } else {
+        (inner, None)
+    };
+
+    let items: Vec<ListItem> = types
+        .iter()
+        .map(|ty| {
+            let is_root = root_types.iter().flatten().any(|name| *name == ty.name);
+            let name_style = if is_root {
+                Style::default()
+                    .fg(app.theme_text_color())
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(app.theme_text_color())
+            };
+            ListItem::new(Line::from(vec![
+                Span::styled(ty.name.clone(), name_style),
+                Span::styled(
+                    format!(" {}", ty.kind.to_lowercase()),
+                    Style::default().fg(app.theme_muted_color()),
+                ),
+            ]))
+        })
+        .collect();
+
+    if items.is_empty() {
+        let placeholder =
+            Paragraph::new("No matches found.").style(Style::default().fg(app.theme_muted_color()));
+        frame.render_widget(placeholder, list_area);
+    } else {
+        let mut state = ListState::default().with_selected(Some(explorer.selected_type));
+        let list = List::new(items).highlight_style(selection_style(app, focused));
+        frame.render_stateful_widget(list, list_area, &mut state);
+    }
+
+    if let Some(filter_area) = filter_area {
+        let style = if explorer.filter_active {
+            Style::default().fg(app.accent_color())
+        } else {
+            Style::default().fg(app.theme_muted_color())
+        };
+        let cursor = if explorer.filter_active { "█" } else { "" };
+        let filter = Paragraph::new(format!("/ {}{}", explorer.filter, cursor)).style(style);
+        frame.render_widget(filter, filter_area);
+    }
+}
+
+fn draw_fields(frame: &mut Frame, app: &App, area: Rect) {
+    let explorer = &app.graphql_explorer;
+    let focused = explorer.pane == GraphQLExplorerPane::Fields;
+    let selected_type = app.graphql_selected_type();
+
+    let title = selected_type.map_or("Fields", |ty| ty.name.as_str());
+    let block = pane_block(app, title, focused);
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let Some(ty) = selected_type else {
+        return;
+    };
+
+    // Reserve space at the bottom for the selected field's description
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(1), Constraint::Length(3)])
+        .split(inner);
+
+    let items: Vec<ListItem> = ty
+        .fields
+        .iter()
+        .map(|field| {
+            let mut spans = vec![Span::styled(
+                field.name.clone(),
+                Style::default().fg(app.theme_text_color()),
+            )];
+            if !field.args.is_empty() {
+                let args: Vec<String> = field
+                    .args
+                    .iter()
+                    .map(|arg| format!("{}: {}", arg.name, arg.type_name))
+                    .collect();
+                spans.push(Span::styled(
+                    format!("({})", args.join(", ")),
+                    Style::default().fg(Color::Yellow),
+                ));
+            }
+            if !field.type_name.is_empty() {
+                spans.push(Span::styled(
+                    format!(": {}", field.type_name),
+                    Style::default().fg(Color::Cyan),
+                ));
+            }
+            ListItem::new(Line::from(spans))
+        })
+        .collect();
+
+    if items.is_empty() {
+        let placeholder =
+            Paragraph::new("No fields.").style(Style::default().fg(app.theme_muted_color()));
+        frame.render_widget(placeholder, chunks[0]);
+        return;
+    }
+
+    let mut state = ListState::default().with_selected(Some(explorer.selected_field));
+    let list = List::new(items).highlight_style(selection_style(app, focused));
+    frame.render_stateful_widget(list, chunks[0], &mut state);
+
+    let description = ty
+        .fields
+        .get(explorer.selected_field)
+        .and_then(|field| field.description.clone())
+        .or_else(|| ty.description.clone())
+        .unwrap_or_default();
+    let description = Paragraph::new(description)
+        .style(Style::default().fg(app.theme_muted_color()))
+        .wrap(Wrap { trim: true })
+        .block(
+            Block::default()
+                .borders(Borders::TOP)
+                .border_style(Style::default().fg(app.theme_muted_color())),
+        );
+    frame.render_widget(description, chunks[1]);
+}
+
+fn pane_block<'a>(app: &App, title: &'a str, focused: bool) -> Block<'a> {
+    let border_color = if focused {
+        app.accent_color()
+    } else {
+        app.theme_muted_color()
+    };
+    Block::default()
+        .title(format!(" {} ", title))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(border_color))
+}
+
+fn selection_style(app: &App, focused: bool) -> Style {
+    if focused {
+        Style::default()
+            .fg(app.theme_selection_fg())
+            .bg(app.theme_selection_bg())
+            .add_modifier(Modifier::BOLD)
+    } else {
+        Style::default().add_modifier(Modifier::REVERSED)
+    }
+}
+
+fn centered_rect(width: u16, height: u16, area: Rect) -> Rect {
+    let horizontal = Layout::horizontal([Constraint::Length(width)]).flex(Flex::Center);
+    let vertical = Layout::vertical([Constraint::Length(height)]).flex(Flex::Center);
+
+    let [area] = vertical.areas(area);
+    let [area] = horizontal.areas(area);
+    area
+}
@@ -0,0 +1,87 @@
+use crate::app::App;
+use ratatui::{
+    layout::{Alignment, Constraint, Flex, Layout, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+pub fn draw_header_presets(frame: &mut Frame, app: &App) {
+    let accent = app.accent_color();
+    let theme = app.theme();
+    let presets = &app.settings.header_presets;
+
+    let popup_width = frame.area().width.saturating_sub(4).clamp(30, 60);
+    let popup_height = (presets.len() + 4).clamp(7, 15) as u16;
+    let area = centered_rect(popup_width, popup_height, frame.area());
+    frame.render_widget(Clear, area);
+
+    let mut lines = Vec::new();
+    for (idx, preset) in presets.iter().enumerate() {
+        let is_selected = idx == app.header_presets_selected;
+        let (name_style, keys_style) = if is_selected {
+            let style = Style::default()
+                .fg(app.theme_selection_fg())
+                .bg(app.theme_selection_bg());
+            (style, style)
+        } else {
+            (
+                Style::default().fg(app.theme_text_color()),
+                Style::default().fg(app.theme_muted_color()),
+            )
+        };
+
+        // Show which headers the preset adds next to its name
+        let keys: Vec<&str> = preset
+            .headers
+            .iter()
+            .map(|header| header.key.as_str())
+            .collect();
+        lines.push(Line::from(vec![
+            Span::styled(format!(" {} ", preset.name), name_style),
+            Span::styled(format!("{} ", keys.join(", ")), keys_style),
+        ]));
+    }
+
+    if lines.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "No header presets",
+            Style::default().fg(app.theme_muted_color()),
+        )));
+    }
+
+    let block = Block::default()
+        .title(" Header Presets ")
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(accent))
+        .style(Style::default().bg(theme.surface));
+
+    let content = Paragraph::new(lines)
+        .block(block)
+        .alignment(Alignment::Left);
+    frame.render_widget(content, area);
+
+    let footer_area = Rect {
+        x: area.x,
+        y: area.y + area.height - 1,
+        width: area.width,
+        height: 1,
+    };
+    let footer = Paragraph::new(Line::from(vec![Span::styled(
+        " Enter add • + save current • Esc close ",
+        Style::default().fg(app.theme_muted_color()),
+    )]))
+    .alignment(Alignment::Center);
+    frame.render_widget(footer, footer_area);
+}
+
+fn centered_rect(width: u16, height: u16, area: Rect) -> Rect {
+    let horizontal = Layout::horizontal([Constraint::Length(width)]).flex(Flex::Center);
+    let vertical = Layout::vertical([Constraint::Length(height)]).flex(Flex::Center);
+
+    let [area] = vertical.areas(area);
+    let [area] = horizontal.areas(area);
+    area
+}
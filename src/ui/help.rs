@@ -15,12 +15,12 @@ pub fn draw_help(frame: &mut Frame, app: &App) {
     // Calculate popup size
     let max_key_len = help_content
         .iter()
-        .map(|(k, _)| k.len())
+        .map(|(k, _)| k.chars().count())
         .max()
         .unwrap_or(10);
     let max_desc_len = help_content
         .iter()
-        .map(|(_, d)| d.len())
+        .map(|(_, d)| d.chars().count())
         .max()
         .unwrap_or(20);
 
@@ -41,10 +41,10 @@ pub fn draw_help(frame: &mut Frame, app: &App) {
                     Style::default().fg(accent).add_modifier(Modifier::BOLD),
                 ))
             } else {
-                // Key-value pair
+                // Key column sized to the longest key, description beside it
                 Line::from(vec![
                     Span::styled(
-                        format!("{:>12}", key),
+                        format!("{:>width$}", key, width = max_key_len),
                         Style::default().fg(accent).add_modifier(Modifier::BOLD),
                     ),
                     Span::raw("  "),
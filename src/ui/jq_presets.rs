@@ -0,0 +1,156 @@
+use super::request_list::highlight_matches;
+use crate::app::App;
+use ratatui::{
+    layout::{Alignment, Constraint, Flex, Layout, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+pub fn draw_jq_presets(frame: &mut Frame, app: &App) {
+    let accent = app.accent_color();
+    let theme = app.theme();
+    let presets = &app.settings.jq_presets;
+
+    let name_width = presets
+        .iter()
+        .map(|preset| preset.name.chars().count())
+        .max()
+        .unwrap_or(10);
+    let max_row_len = presets
+        .iter()
+        .map(|preset| name_width + preset.filter.chars().count() + 4)
+        .max()
+        .unwrap_or(20);
+
+    let popup_width = (max_row_len + 4).clamp(36, 70) as u16;
+    let show_search = app.jq_presets_search_active || !app.jq_presets_search_query.is_empty();
+    let popup_height = (presets.len() + 5 + usize::from(show_search)).clamp(8, 18) as u16;
+    let area = centered_rect(popup_width, popup_height, frame.area());
+    frame.render_widget(Clear, area);
+
+    // Rows left for presets once the description and search lines are placed
+    let visible_rows = popup_height.saturating_sub(4 + u16::from(show_search)) as usize;
+    let offset = app
+        .jq_presets_selected
+        .saturating_sub(visible_rows.saturating_sub(1));
+
+    let mut lines = Vec::new();
+    let filtered = app.filtered_jq_preset_indices();
+    for (idx, preset) in filtered
+        .iter()
+        .filter_map(|&idx| presets.get(idx))
+        .enumerate()
+        .skip(offset)
+        .take(visible_rows)
+    {
+        let is_selected = idx == app.jq_presets_selected;
+        let line_style = if is_selected {
+            Style::default()
+                .fg(app.theme_selection_fg())
+                .bg(app.theme_selection_bg())
+        } else {
+            Style::default().fg(app.theme_text_color())
+        };
+        let filter_style = if is_selected {
+            line_style
+        } else {
+            Style::default().fg(app.theme_muted_color())
+        };
+
+        let mut spans = vec![Span::styled(" ", line_style)];
+        spans.extend(highlight_matches(
+            &format!("{:<width$}", preset.name, width = name_width),
+            &app.jq_presets_search_query,
+            line_style,
+            accent,
+        ));
+        spans.push(Span::styled("  ", line_style));
+        spans.extend(highlight_matches(
+            &preset.filter,
+            &app.jq_presets_search_query,
+            filter_style,
+            accent,
+        ));
+        spans.push(Span::styled(" ", line_style));
+        lines.push(Line::from(spans));
+    }
+
+    if lines.is_empty() {
+        let message = if presets.is_empty() {
+            "No presets — press + to save the current filter"
+        } else {
+            "No matches"
+        };
+        lines.push(Line::from(Span::styled(
+            message,
+            Style::default().fg(app.theme_muted_color()),
+        )));
+    }
+
+    // Description of the selected preset, kept just above the search line
+    let description = filtered
+        .get(app.jq_presets_selected)
+        .and_then(|&idx| presets.get(idx))
+        .map(|preset| preset.description.as_str())
+        .unwrap_or_default();
+    while lines.len() < visible_rows {
+        lines.push(Line::from(""));
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        format!(" {}", description),
+        Style::default().fg(app.theme_muted_color()),
+    )));
+
+    if show_search {
+        let cursor = if app.jq_presets_search_active {
+            "█"
+        } else {
+            ""
+        };
+        lines.push(Line::from(Span::styled(
+            format!(" / {}{}", app.jq_presets_search_query, cursor),
+            Style::default().fg(accent),
+        )));
+    }
+
+    let block = Block::default()
+        .title(" jq Presets ")
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(accent))
+        .style(Style::default().bg(theme.surface));
+
+    let content = Paragraph::new(lines)
+        .block(block)
+        .alignment(Alignment::Left);
+    frame.render_widget(content, area);
+
+    let footer_area = Rect {
+        x: area.x,
+        y: area.y + area.height - 1,
+        width: area.width,
+        height: 1,
+    };
+    let footer = Paragraph::new(Line::from(vec![Span::styled(
+        if app.jq_presets_search_active {
+            " Enter done • Esc clear "
+        } else {
+            " Enter apply • / search • + save filter • d delete • Esc close "
+        },
+        Style::default().fg(app.theme_muted_color()),
+    )]))
+    .alignment(Alignment::Center);
+    frame.render_widget(footer, footer_area);
+}
+
+fn centered_rect(width: u16, height: u16, area: Rect) -> Rect {
+    let horizontal = Layout::horizontal([Constraint::Length(width)]).flex(Flex::Center);
+    let vertical = Layout::vertical([Constraint::Length(height)]).flex(Flex::Center);
+
+    let [area] = vertical.areas(area);
+    let [area] = horizontal.areas(area);
+    area
+}
@@ -31,10 +31,13 @@ pub fn draw_layout(frame: &mut Frame, app: &mut App) {
     // Draw header
     draw_header(frame, app, chunks[0]);
 
-    // Main horizontal layout: left panel (30%), right panel (70%)
+    // Main horizontal layout: left panel and right panel (split is resizable by dragging)
     let main_chunks = Layout::default()
         .direction(Direction::Horizontal)
-        .constraints([Constraint::Percentage(30), Constraint::Percentage(70)])
+        .constraints([
+            Constraint::Percentage(app.layout_split_h),
+            Constraint::Percentage(100 - app.layout_split_h),
+        ])
         .split(chunks[1]);
 
     // Store layout areas for mouse click detection
@@ -57,9 +60,9 @@ pub fn draw_layout(frame: &mut Frame, app: &mut App) {
             Constraint::Min(5),     // Response viewer expanded
         ],
         _ => [
-            Constraint::Length(3),      // URL bar
-            Constraint::Percentage(40), // Request editor
-            Constraint::Min(5),         // Response viewer (fills remaining space)
+            Constraint::Length(3),                      // URL bar
+            Constraint::Percentage(app.layout_split_v), // Request editor
+            Constraint::Min(5),                         // Response viewer (fills remaining space)
         ],
     };
 
@@ -99,7 +102,7 @@ fn draw_header(frame: &mut Frame, app: &App, area: Rect) {
     let theme = app.theme();
     let accent = app.accent_color();
 
-    let left_content = Line::from(vec![
+    let mut left_content = Line::from(vec![
         Span::styled(
             " ResTUI ",
             Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
@@ -111,6 +114,12 @@ fn draw_header(frame: &mut Frame, app: &App, area: Rect) {
                 .add_modifier(Modifier::BOLD),
         ),
     ]);
+    if let Some(latest) = &app.update_available {
+        left_content.push_span(Span::styled(
+            format!("  v{} available", latest),
+            Style::default().fg(theme.muted),
+        ));
+    }
     let left_header = Paragraph::new(left_content).style(Style::default().bg(theme.surface));
     frame.render_widget(left_header, header_chunks[0]);
 
@@ -150,6 +159,51 @@ fn draw_footer(frame: &mut Frame, app: &App, area: Rect) {
     // Build footer: mode indicator + optional status + shortcuts
     let mut footer_spans = vec![mode_indicator, Span::raw(" ")];
 
+    if app.settings.readonly_mode {
+        footer_spans.push(Span::styled(
+            "[RO]",
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        ));
+        footer_spans.push(Span::raw(" "));
+    }
+
+    if app.verbose_mode {
+        footer_spans.push(Span::styled(
+            " VERBOSE ",
+            Style::default().bg(Color::Magenta).fg(Color::Black),
+        ));
+        footer_spans.push(Span::raw(" "));
+    }
+
+    if let Some(line) = app.scroll_anchor {
+        footer_spans.push(Span::styled(
+            format!("[anchored Ln {}]", line + 1),
+            Style::default().fg(Color::Cyan),
+        ));
+        footer_spans.push(Span::raw(" "));
+    }
+
+    if let Some(remaining) = app.rate_limit_remaining() {
+        footer_spans.push(Span::styled(
+            " RATE LIMITED ",
+            Style::default()
+                .bg(Color::Red)
+                .fg(Color::Black)
+                .add_modifier(Modifier::BOLD),
+        ));
+        let seconds = remaining.as_secs() + u64::from(remaining.subsec_nanos() > 0);
+        let countdown = if app.rate_limit_retry_pending || app.rate_limit_background_retry.is_some()
+        {
+            format!(" Rate limited — retrying in {}s ", seconds)
+        } else {
+            format!(" Rate limited for {}s ", seconds)
+        };
+        footer_spans.push(Span::styled(countdown, Style::default().fg(Color::Red)));
+        footer_spans.push(Span::raw(" "));
+    }
+
     // Show status/error message if present
     if app.is_loading {
         footer_spans.push(Span::styled(
@@ -167,6 +221,15 @@ fn draw_footer(frame: &mut Frame, app: &App, area: Rect) {
             "│ ",
             Style::default().fg(app.theme_muted_color()),
         ));
+    } else if let Some(warning) = &app.warning_message {
+        footer_spans.push(Span::styled(
+            format!("{} ", warning),
+            Style::default().fg(Color::Yellow),
+        ));
+        footer_spans.push(Span::styled(
+            "│ ",
+            Style::default().fg(app.theme_muted_color()),
+        ));
     } else if let Some(status) = &app.status_message {
         footer_spans.push(Span::styled(
             format!("{} ", status),
@@ -178,16 +241,58 @@ fn draw_footer(frame: &mut Frame, app: &App, area: Rect) {
         ));
     }
 
-    // Always show shortcuts (except when loading)
-    if !app.is_loading {
+    // A hovered request's description replaces the shortcuts
+    if let Some(description) = app.hovered_description().filter(|_| !app.is_loading) {
+        let used = Line::from(footer_spans.clone()).width();
+        let available = (area.width as usize).saturating_sub(used + 1);
+        let description = if description.chars().count() > available {
+            let truncated: String = description
+                .chars()
+                .take(available.saturating_sub(1))
+                .collect();
+            format!("{}…", truncated)
+        } else {
+            description.to_string()
+        };
+        footer_spans.push(Span::styled(
+            description,
+            Style::default()
+                .fg(app.theme_muted_color())
+                .add_modifier(Modifier::ITALIC),
+        ));
+    } else if !app.is_loading {
+        // Always show shortcuts (except when loading)
         footer_spans.extend(get_panel_shortcuts(app));
     }
 
     let footer_content = Line::from(footer_spans);
+    let footer_width = footer_content.width();
     let footer =
         Paragraph::new(footer_content).style(Style::default().bg(app.theme_surface_color()));
 
     frame.render_widget(footer, area);
+
+    // Context hint on the right, when there is room for it
+    let mut hint = Line::default();
+    if let Some(response) = &app.response {
+        hint.push_span(Span::styled(
+            format!("Last response: {}ms  ", response.duration_ms),
+            Style::default().fg(app.theme_muted_color()),
+        ));
+    }
+    hint.extend([
+        Span::styled(
+            app.get_context_hint(),
+            Style::default().fg(app.theme_muted_color()),
+        ),
+        Span::styled(
+            "  Press ? for help ",
+            Style::default().fg(app.theme_accent_color()),
+        ),
+    ]);
+    if !app.is_loading && footer_width + hint.width() + 2 <= area.width as usize {
+        frame.render_widget(Paragraph::new(hint).right_aligned(), area);
+    }
 }
 
 /// Build a shortcut hint span with highlighted key
@@ -217,6 +322,7 @@ fn get_panel_shortcuts(app: &App) -> Vec<Span<'static>> {
                         spans.extend(shortcut("Esc", "cancel", accent, muted));
                     } else if app.has_request_list_filter() {
                         spans.extend(shortcut("Enter", "select", accent, muted));
+                        spans.extend(shortcut("n/N", "match", accent, muted));
                         spans.extend(shortcut("s", "send", accent, muted));
                         spans.extend(shortcut("Esc", "clear", accent, muted));
                     } else {
@@ -224,6 +330,7 @@ fn get_panel_shortcuts(app: &App) -> Vec<Span<'static>> {
                         spans.extend(shortcut("s", "send", accent, muted));
                         spans.extend(shortcut("Space", "expand", accent, muted));
                         spans.extend(shortcut("H", "history", accent, muted));
+                        spans.extend(shortcut("I", "import curl", accent, muted));
                     }
                 }
                 FocusedPanel::UrlBar => {
@@ -240,13 +347,27 @@ fn get_panel_shortcuts(app: &App) -> Vec<Span<'static>> {
                     match app.request_tab {
                         RequestTab::Body => {
                             spans.extend(shortcut("f", "format", accent, muted));
+                            spans.extend(shortcut("b", "body type", accent, muted));
+                            spans.extend(shortcut("I", "import", accent, muted));
+                            if app.body_format_label() == "GraphQL" {
+                                spans.extend(shortcut("G", "schema", accent, muted));
+                            }
                         }
                         RequestTab::Auth => {
                             spans.extend(shortcut("a", "auth type", accent, muted));
+                            spans.extend(shortcut("d", "jwt", accent, muted));
+                            spans.extend(shortcut("C", "collection auth", accent, muted));
+                        }
+                        RequestTab::CollectionAuth => {
+                            spans.extend(shortcut("a", "auth type", accent, muted));
                         }
+                        RequestTab::Settings => {}
                         RequestTab::Headers | RequestTab::Params => {
                             spans.extend(shortcut("t", "toggle", accent, muted));
                             spans.extend(shortcut("x", "delete", accent, muted));
+                            if app.request_tab == RequestTab::Headers {
+                                spans.extend(shortcut("P", "presets", accent, muted));
+                            }
                         }
                     }
                 }
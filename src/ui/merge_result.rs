@@ -0,0 +1,93 @@
+use crate::app::App;
+use crate::storage::MergeResult;
+use ratatui::{
+    layout::{Alignment, Constraint, Flex, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+pub fn draw_merge_result(frame: &mut Frame, app: &App, result: &MergeResult) {
+    let accent = app.accent_color();
+    let theme = app.theme();
+    let heading = |text: String| {
+        Line::from(Span::styled(
+            text,
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        ))
+    };
+
+    let mut lines = vec![heading(format!("Added ({})", result.added.len()))];
+    if result.added.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "  Nothing new",
+            Style::default().fg(app.theme_muted_color()),
+        )));
+    }
+    for name in &result.added {
+        lines.push(Line::from(vec![
+            Span::styled("  + ", Style::default().fg(Color::Green)),
+            Span::styled(name.clone(), Style::default().fg(app.theme_text_color())),
+        ]));
+    }
+
+    lines.push(Line::from(""));
+    lines.push(heading(format!("Conflicts ({})", result.conflicts.len())));
+    if result.conflicts.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "  None",
+            Style::default().fg(app.theme_muted_color()),
+        )));
+    }
+    for (existing, imported) in &result.conflicts {
+        lines.push(Line::from(vec![
+            Span::styled("  ! ", Style::default().fg(Color::Yellow)),
+            Span::styled(
+                existing.name.clone(),
+                Style::default().fg(app.theme_text_color()),
+            ),
+            Span::styled(
+                format!(" kept; imported as \"{}\"", imported.name),
+                Style::default().fg(app.theme_muted_color()),
+            ),
+        ]));
+    }
+
+    let popup_width = frame.area().width.saturating_sub(4).clamp(30, 70);
+    let popup_height = (lines.len() as u16 + 3).clamp(8, frame.area().height.max(8));
+    let area = centered_rect(popup_width, popup_height, frame.area());
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(format!(" Merged into {} ", result.merged.name))
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(accent))
+        .style(Style::default().bg(theme.surface));
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+
+    let footer_area = Rect {
+        x: area.x,
+        y: area.y + area.height - 1,
+        width: area.width,
+        height: 1,
+    };
+    let footer = Paragraph::new(Line::from(vec![Span::styled(
+        " Enter/Esc close ",
+        Style::default().fg(app.theme_muted_color()),
+    )]))
+    .alignment(Alignment::Center);
+    frame.render_widget(footer, footer_area);
+}
+
+fn centered_rect(width: u16, height: u16, area: Rect) -> Rect {
+    let horizontal = Layout::horizontal([Constraint::Length(width)]).flex(Flex::Center);
+    let vertical = Layout::vertical([Constraint::Length(height)]).flex(Flex::Center);
+
+    let [area] = vertical.areas(area);
+    let [area] = horizontal.areas(area);
+    area
+}
@@ -1,30 +1,80 @@
+mod batch_results;
+mod context_menu;
+mod copy_as;
 mod dialog;
+mod encoding_popup;
+mod env_diff;
 mod env_popup;
 mod filter_history;
+mod graphql_explorer;
+mod header_presets;
 mod help;
+mod jq_presets;
 mod layout;
+mod merge_result;
+mod readme_editor;
 mod request_editor;
 mod request_list;
 mod response;
+mod response_stats;
+mod schema_errors;
+mod settings_popup;
 mod theme_popup;
 mod url_bar;
+mod url_builder;
+mod verbose_preview;
 pub mod widgets;
 
-use crate::app::App;
+use crate::app::{App, EditingField};
 use ratatui::Frame;
 
 pub fn draw(frame: &mut Frame, app: &mut App) {
     layout::draw_layout(frame, app);
 
+    // URL history suggestions float below the URL bar while cycling
+    if app.url_history_cycle.is_some() && app.editing_field == Some(EditingField::Url) {
+        url_bar::draw_url_history(frame, app);
+    }
+
     // Draw dialog popup on top if showing (higher priority than help)
     if app.dialog.dialog_type.is_some() {
         dialog::draw_dialog(frame, app);
+    } else if app.readme_editor.is_some() {
+        readme_editor::draw_readme_editor(frame, app);
+    } else if app.show_verbose_preview {
+        verbose_preview::draw_verbose_preview(frame, app);
     } else if app.show_env_popup {
         env_popup::draw_env_popup(frame, app);
+    } else if app.show_settings_popup {
+        settings_popup::draw_settings_popup(frame, app);
     } else if app.show_theme_popup {
         theme_popup::draw_theme_popup(frame, app);
+    } else if app.show_graphql_explorer {
+        graphql_explorer::draw_graphql_explorer(frame, app);
+    } else if app.show_header_presets {
+        header_presets::draw_header_presets(frame, app);
+    } else if app.show_copy_as {
+        copy_as::draw_copy_as(frame, app);
+    } else if app.show_context_menu {
+        context_menu::draw_context_menu(frame, app);
+    } else if app.url_builder_mode {
+        url_builder::draw_url_builder(frame, app);
+    } else if app.show_encoding_popup {
+        encoding_popup::draw_encoding_popup(frame, app);
+    } else if let Some(result) = &app.merge_result {
+        merge_result::draw_merge_result(frame, app, result);
+    } else if let Some(popup) = &app.env_diff_popup {
+        env_diff::draw_env_diff(frame, app, popup);
+    } else if let Some(popup) = &app.batch_results {
+        batch_results::draw_batch_results(frame, app, popup);
+    } else if app.show_schema_errors {
+        schema_errors::draw_schema_errors(frame, app);
+    } else if let Some(stats) = &app.response_stats {
+        response_stats::draw_response_stats(frame, app, stats);
     } else if app.show_filter_history {
         filter_history::draw_filter_history(frame, app);
+    } else if app.show_jq_presets {
+        jq_presets::draw_jq_presets(frame, app);
     } else if app.show_help {
         help::draw_help(frame, app);
     }
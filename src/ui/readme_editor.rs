@@ -0,0 +1,96 @@
+use crate::app::App;
+use ratatui::{
+    layout::{Alignment, Margin, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use super::widgets::text_with_cursor_and_selection;
+
+pub fn draw_readme_editor(frame: &mut Frame, app: &App) {
+    let Some(editor) = &app.readme_editor else {
+        return;
+    };
+    let accent = app.accent_color();
+    let name = app
+        .collections
+        .get(editor.collection_index)
+        .map(|collection| collection.name.as_str())
+        .unwrap_or_default();
+
+    let area = frame.area().inner(Margin {
+        horizontal: 2,
+        vertical: 1,
+    });
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(format!(" README: {} ", name))
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(accent))
+        .style(Style::default().bg(app.theme_surface_color()));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let text_style = Style::default().fg(app.theme_text_color());
+    let cursor = app.cursor_position.min(editor.text.chars().count());
+    let selection = app
+        .get_selection_range()
+        .filter(|(start, end)| start != end);
+
+    let mut lines = Vec::new();
+    let mut cursor_line = 0;
+    let mut line_start = 0;
+    for (index, text) in editor.text.split('\n').enumerate() {
+        let line_end = line_start + text.chars().count();
+        let has_cursor = (line_start..=line_end).contains(&cursor);
+        if has_cursor {
+            cursor_line = index;
+        }
+        // Only the part of the selection on this line, relative to its start
+        let line_selection = selection
+            .filter(|(start, end)| *start <= line_end && *end >= line_start)
+            .map(|(start, end)| {
+                (
+                    start.saturating_sub(line_start),
+                    end.min(line_end) - line_start,
+                )
+            });
+        lines.push(Line::from(text_with_cursor_and_selection(
+            text,
+            cursor.saturating_sub(line_start),
+            has_cursor,
+            "",
+            text_style,
+            line_selection,
+        )));
+        line_start = line_end + 1;
+    }
+
+    // Keep the cursor line on screen
+    let height = inner.height.saturating_sub(1) as usize;
+    let scroll = cursor_line.saturating_sub(height.saturating_sub(1));
+    frame.render_widget(
+        Paragraph::new(lines).scroll((scroll as u16, 0)),
+        Rect {
+            height: inner.height.saturating_sub(1),
+            ..inner
+        },
+    );
+
+    let footer_area = Rect {
+        x: area.x,
+        y: area.y + area.height - 1,
+        width: area.width,
+        height: 1,
+    };
+    let footer = Paragraph::new(Line::from(vec![Span::styled(
+        " Markdown or plain text • Esc save & close ",
+        Style::default().fg(app.theme_muted_color()),
+    )]))
+    .alignment(Alignment::Center);
+    frame.render_widget(footer, footer_area);
+}
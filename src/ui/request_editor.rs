@@ -1,5 +1,6 @@
 use crate::app::{App, EditingField, FocusedPanel, InputMode, RequestTab};
-use crate::storage::AuthType;
+use crate::jwt::decode_jwt;
+use crate::storage::{AuthConfig, AuthType, BodyType, OAuth2Field};
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
@@ -44,13 +45,19 @@ pub fn draw(frame: &mut Frame, app: &mut App, area: Rect) {
     match app.request_tab {
         RequestTab::Headers => draw_headers(frame, app, chunks[1], accent),
         RequestTab::Body => draw_body(frame, app, chunks[1]),
-        RequestTab::Auth => draw_auth(frame, app, chunks[1], accent),
+        RequestTab::Auth => draw_request_auth(frame, app, chunks[1], accent),
         RequestTab::Params => draw_params(frame, app, chunks[1], accent),
+        RequestTab::Settings => draw_settings(frame, app, chunks[1]),
+        RequestTab::CollectionAuth => draw_collection_auth(frame, app, chunks[1], accent),
     }
 }
 
 fn draw_tabs(frame: &mut Frame, app: &mut App, area: Rect, accent: Color) {
-    let tabs_list = RequestTab::all();
+    // The Collection Auth tab only appears while it is open
+    let mut tabs_list = RequestTab::all().to_vec();
+    if app.request_tab == RequestTab::CollectionAuth {
+        tabs_list.push(RequestTab::CollectionAuth);
+    }
     let titles: Vec<Line> = tabs_list
         .iter()
         .map(|t| {
@@ -68,7 +75,7 @@ fn draw_tabs(frame: &mut Frame, app: &mut App, area: Rect, accent: Color) {
     // Each tab has its text width, plus separator " | " (3 chars) between tabs
     let mut tab_positions = Vec::new();
     let mut current_x = area.x;
-    for tab in tabs_list {
+    for tab in &tabs_list {
         let tab_width = tab.as_str().len() as u16;
         tab_positions.push((current_x, tab_width, *tab));
         // Add tab width + separator " | " (3 chars)
@@ -160,117 +167,167 @@ fn draw_headers(frame: &mut Frame, app: &App, area: Rect, accent: Color) {
     frame.render_widget(para, area);
 }
 
-fn draw_body(frame: &mut Frame, app: &mut App, area: Rect) {
-    let is_editing =
-        app.input_mode == InputMode::Editing && app.editing_field == Some(EditingField::Body);
+/// Lines of a multiline field being edited, with the cursor and selection drawn.
+/// `highlights` adds the body search matches and bracket match.
+fn editing_lines(app: &App, text: &str, highlights: bool) -> Vec<Line<'static>> {
+    // When editing, we need to show cursor and selection across lines
+    let char_count = text.chars().count();
+    let cursor_pos = app.cursor_position.min(char_count);
+    let selection = app.get_selection_range();
+    let mut result_lines = Vec::new();
+    let mut line_char_start = 0;
+
+    let editing_style = Style::default().bg(Color::DarkGray);
+    let cursor_style = Style::default().bg(Color::White).fg(Color::Black);
+    let selection_style = Style::default().bg(Color::Blue).fg(Color::White);
+    let match_style = Style::default().bg(Color::Rgb(60, 70, 100));
+    let current_match_style = Style::default()
+        .bg(app.theme_selection_bg())
+        .fg(app.theme_selection_fg());
+    let current_match_line = app
+        .body_search_matches
+        .get(app.body_search_current)
+        .copied();
+    let bracket_style = Style::default()
+        .bg(Color::DarkGray)
+        .add_modifier(Modifier::UNDERLINED | Modifier::BOLD);
+
+    for (line_idx, line_text) in text.split('\n').enumerate() {
+        // Lines matching the body search get a subtle background
+        let editing_style = if !highlights {
+            editing_style
+        } else if current_match_line == Some(line_idx) {
+            current_match_style
+        } else if app.body_search_matches.contains(&line_idx) {
+            match_style
+        } else {
+            editing_style
+        };
+        let line_char_count = line_text.chars().count();
+        let line_char_end = line_char_start + line_char_count;
 
-    let body = &app.current_request.body;
+        let mut spans: Vec<Span> = Vec::new();
+        let chars: Vec<char> = line_text.chars().collect();
 
-    let lines: Vec<Line> = if body.is_empty() && !is_editing {
-        vec![Line::from(Span::styled(
-            "Enter request body...",
-            Style::default().fg(app.theme_muted_color()),
-        ))]
-    } else if is_editing {
-        // When editing, we need to show cursor and selection across lines
-        let char_count = body.chars().count();
-        let cursor_pos = app.cursor_position.min(char_count);
-        let selection = app.get_selection_range();
-        let mut result_lines = Vec::new();
-        let mut line_char_start = 0;
-
-        let editing_style = Style::default().bg(Color::DarkGray);
-        let cursor_style = Style::default().bg(Color::White).fg(Color::Black);
-        let selection_style = Style::default().bg(Color::Blue).fg(Color::White);
-
-        for line_text in body.split('\n') {
-            let line_char_count = line_text.chars().count();
-            let line_char_end = line_char_start + line_char_count;
-
-            let mut spans: Vec<Span> = Vec::new();
-            let chars: Vec<char> = line_text.chars().collect();
-
-            // Determine if cursor is on this line
-            let cursor_on_line = cursor_pos >= line_char_start && cursor_pos <= line_char_end;
-            let cursor_in_line = if cursor_on_line {
-                Some(cursor_pos - line_char_start)
-            } else {
-                None
-            };
+        // Determine if cursor is on this line
+        let cursor_on_line = cursor_pos >= line_char_start && cursor_pos <= line_char_end;
+        let cursor_in_line = if cursor_on_line {
+            Some(cursor_pos - line_char_start)
+        } else {
+            None
+        };
 
-            // Check if we have a selection that overlaps this line
-            let has_selection = selection.map(|(s, e)| s != e).unwrap_or(false);
-
-            if has_selection {
-                let (sel_start, sel_end) = selection.unwrap();
-                // Calculate selection overlap with this line
-                let line_sel_start = sel_start
-                    .saturating_sub(line_char_start)
-                    .min(line_char_count);
-                let line_sel_end = sel_end.saturating_sub(line_char_start).min(line_char_count);
-
-                if line_sel_end > 0 && sel_start < line_char_end && sel_end > line_char_start {
-                    // Selection overlaps this line
-                    if line_sel_start > 0 {
-                        let before: String = chars[..line_sel_start].iter().collect();
-                        spans.push(Span::styled(before, editing_style));
-                    }
-
-                    if line_sel_end > line_sel_start {
-                        let selected: String = chars[line_sel_start..line_sel_end].iter().collect();
-                        spans.push(Span::styled(selected, selection_style));
-                    }
-
-                    if line_sel_end < line_char_count {
-                        let after: String = chars[line_sel_end..].iter().collect();
-                        spans.push(Span::styled(after, editing_style));
-                    }
-
-                    // Add cursor block at end of line if cursor is past text
-                    if cursor_on_line && cursor_in_line.unwrap() >= line_char_count {
-                        spans.push(Span::styled(" ", cursor_style));
-                    }
-                } else {
-                    // No selection on this line
-                    spans.push(Span::styled(line_text.to_string(), editing_style));
-                    if cursor_on_line && cursor_in_line.unwrap() >= line_char_count {
-                        spans.push(Span::styled(" ", cursor_style));
-                    }
+        // Check if we have a selection that overlaps this line
+        let has_selection = selection.map(|(s, e)| s != e).unwrap_or(false);
+
+        if has_selection {
+            let (sel_start, sel_end) = selection.unwrap();
+            // Calculate selection overlap with this line
+            let line_sel_start = sel_start
+                .saturating_sub(line_char_start)
+                .min(line_char_count);
+            let line_sel_end = sel_end.saturating_sub(line_char_start).min(line_char_count);
+
+            if line_sel_end > 0 && sel_start < line_char_end && sel_end > line_char_start {
+                // Selection overlaps this line
+                if line_sel_start > 0 {
+                    let before: String = chars[..line_sel_start].iter().collect();
+                    spans.push(Span::styled(before, editing_style));
                 }
-            } else if let Some(pos_in_line) = cursor_in_line {
-                // No selection, just cursor
-                if pos_in_line >= line_char_count {
-                    // Cursor at end of line
-                    spans.push(Span::styled(line_text.to_string(), editing_style));
+
+                if line_sel_end > line_sel_start {
+                    let selected: String = chars[line_sel_start..line_sel_end].iter().collect();
+                    spans.push(Span::styled(selected, selection_style));
+                }
+
+                if line_sel_end < line_char_count {
+                    let after: String = chars[line_sel_end..].iter().collect();
+                    spans.push(Span::styled(after, editing_style));
+                }
+
+                // Add cursor block at end of line if cursor is past text
+                if cursor_on_line && cursor_in_line.unwrap() >= line_char_count {
                     spans.push(Span::styled(" ", cursor_style));
-                } else {
-                    // Cursor in middle
-                    if pos_in_line > 0 {
-                        let before: String = chars[..pos_in_line].iter().collect();
-                        spans.push(Span::styled(before, editing_style));
-                    }
-                    spans.push(Span::styled(chars[pos_in_line].to_string(), cursor_style));
-                    if pos_in_line + 1 < line_char_count {
-                        let after: String = chars[pos_in_line + 1..].iter().collect();
-                        spans.push(Span::styled(after, editing_style));
-                    }
                 }
             } else {
-                // Line without cursor
+                // No selection on this line
                 spans.push(Span::styled(line_text.to_string(), editing_style));
+                if cursor_on_line && cursor_in_line.unwrap() >= line_char_count {
+                    spans.push(Span::styled(" ", cursor_style));
+                }
             }
-
-            result_lines.push(Line::from(spans));
-            // Account for the newline character
-            line_char_start = line_char_end + 1;
+        } else if let Some(pos_in_line) = cursor_in_line {
+            // No selection, just cursor
+            if pos_in_line >= line_char_count {
+                // Cursor at end of line
+                spans.push(Span::styled(line_text.to_string(), editing_style));
+                spans.push(Span::styled(" ", cursor_style));
+            } else {
+                // Cursor in middle
+                if pos_in_line > 0 {
+                    let before: String = chars[..pos_in_line].iter().collect();
+                    spans.push(Span::styled(before, editing_style));
+                }
+                spans.push(Span::styled(chars[pos_in_line].to_string(), cursor_style));
+                if pos_in_line + 1 < line_char_count {
+                    let after: String = chars[pos_in_line + 1..].iter().collect();
+                    spans.push(Span::styled(after, editing_style));
+                }
+            }
+        } else {
+            // Line without cursor
+            spans.push(Span::styled(line_text.to_string(), editing_style));
         }
 
-        // Handle empty body with cursor
-        if result_lines.is_empty() {
-            result_lines.push(Line::from(Span::styled(" ", cursor_style)));
+        let mut line = Line::from(spans);
+        if let Some(bracket) = app
+            .body_bracket_match
+            .filter(|pos| highlights && (line_char_start..line_char_end).contains(pos))
+        {
+            line = highlight_char(line, bracket - line_char_start, bracket_style);
         }
+        result_lines.push(line);
+        // Account for the newline character
+        line_char_start = line_char_end + 1;
+    }
 
-        result_lines
+    // Handle empty body with cursor
+    if result_lines.is_empty() {
+        result_lines.push(Line::from(Span::styled(" ", cursor_style)));
+    }
+
+    result_lines
+}
+
+fn draw_body(frame: &mut Frame, app: &mut App, mut area: Rect) {
+    let is_editing =
+        app.input_mode == InputMode::Editing && app.editing_field == Some(EditingField::Body);
+    let body_type = app.current_request.body_type;
+
+    // A GraphQL body is the query, with its variables in a pane below
+    if body_type == BodyType::GraphQL {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Percentage(65), Constraint::Percentage(35)])
+            .split(area);
+        draw_graphql_variables(frame, app, chunks[1]);
+        area = chunks[0];
+    }
+
+    let body = &app.current_request.body;
+
+    let lines: Vec<Line> = if body.is_empty() && !is_editing {
+        let placeholder = match body_type {
+            BodyType::Raw => "Enter request body...",
+            BodyType::FormUrlEncoded => "Enter form fields, one key=value per line...",
+            BodyType::GraphQL => "Enter GraphQL query...",
+        };
+        vec![Line::from(Span::styled(
+            placeholder,
+            Style::default().fg(app.theme_muted_color()),
+        ))]
+    } else if is_editing {
+        editing_lines(app, body, true)
     } else {
         // Not editing, just display lines normally
         body.split('\n')
@@ -286,7 +343,62 @@ fn draw_body(frame: &mut Frame, app: &mut App, area: Rect) {
             Style::default().fg(app.theme_muted_color())
         })
         .style(Style::default().bg(app.theme_surface_color()))
-        .title(format!(" Body ({}) ", app.body_format_label()));
+        .title(format!(
+            " {} ({}){}{} ",
+            if body_type == BodyType::GraphQL {
+                "Query"
+            } else {
+                "Body"
+            },
+            match body_type {
+                BodyType::Raw => format!("Raw · {}", app.body_format_label()),
+                other => other.as_str().to_string(),
+            },
+            app.current_request
+                .compress_body
+                .map(|compression| format!(" [{}]", compression.as_str()))
+                .unwrap_or_default(),
+            if app.body_is_response_template() {
+                " [from response]"
+            } else {
+                ""
+            }
+        ));
+    let block = if is_editing && app.body_search_active {
+        let count = app.body_search_matches.len();
+        let position = if count == 0 {
+            "no matches".to_string()
+        } else {
+            format!("{}/{}", app.body_search_current + 1, count)
+        };
+        let cursor = if app.body_search_typing { "█" } else { "" };
+        block.title_bottom(Line::from(vec![
+            Span::styled(" Find: ", Style::default().fg(app.accent_color())),
+            Span::styled(
+                format!("{}{} ", app.body_search_query, cursor),
+                Style::default().fg(app.theme_text_color()),
+            ),
+            Span::styled(
+                format!("[{}] ", position),
+                Style::default().fg(app.theme_muted_color()),
+            ),
+        ]))
+    } else if app.body_goto_line_active {
+        block.title_bottom(Line::from(vec![
+            Span::styled(" Go to line: ", Style::default().fg(app.accent_color())),
+            Span::styled(
+                format!("{}█ ", app.goto_line_query),
+                Style::default().fg(app.theme_text_color()),
+            ),
+        ]))
+    } else if body_type == BodyType::FormUrlEncoded {
+        block.title_bottom(Span::styled(
+            " key=value per line or joined with &, URL-encoded when sent ",
+            Style::default().fg(app.theme_muted_color()),
+        ))
+    } else {
+        block
+    };
 
     // Store inner area for click-to-cursor positioning
     let inner_area = block.inner(area);
@@ -317,8 +429,149 @@ fn draw_body(frame: &mut Frame, app: &mut App, area: Rect) {
     }
 }
 
-fn draw_auth(frame: &mut Frame, app: &App, area: Rect, accent: Color) {
-    let auth = &app.current_request.auth;
+/// Variables of a GraphQL body, as JSON (Tab switches to them from the query)
+fn draw_graphql_variables(frame: &mut Frame, app: &App, area: Rect) {
+    let is_editing = app.input_mode == InputMode::Editing
+        && app.editing_field == Some(EditingField::GraphQLVariables);
+    let variables = &app.current_request.graphql_variables;
+
+    let lines: Vec<Line> = if variables.is_empty() && !is_editing {
+        vec![Line::from(Span::styled(
+            r#"{"id": 1} (Tab from the query to edit)"#,
+            Style::default().fg(app.theme_muted_color()),
+        ))]
+    } else if is_editing {
+        editing_lines(app, variables, false)
+    } else {
+        variables
+            .split('\n')
+            .map(|line| Line::from(Span::raw(line.to_string())))
+            .collect()
+    };
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(if is_editing {
+            Style::default().fg(Color::Green)
+        } else {
+            Style::default().fg(app.theme_muted_color())
+        })
+        .style(Style::default().bg(app.theme_surface_color()))
+        .title(" Variables (JSON) ");
+
+    // Keep the cursor line in view
+    let scroll = if is_editing {
+        let cursor_line = variables
+            .chars()
+            .take(app.cursor_position)
+            .filter(|&c| c == '\n')
+            .count() as u16;
+        cursor_line.saturating_sub(block.inner(area).height.saturating_sub(1))
+    } else {
+        0
+    };
+
+    frame.render_widget(Paragraph::new(lines).block(block).scroll((scroll, 0)), area);
+}
+
+/// Patch `style` onto the character at `col`, splitting the span that holds it
+fn highlight_char(line: Line<'_>, col: usize, style: Style) -> Line<'_> {
+    let mut spans = Vec::with_capacity(line.spans.len() + 2);
+    let mut start = 0;
+    for span in line.spans {
+        let len = span.content.chars().count();
+        if (start..start + len).contains(&col) {
+            let chars: Vec<char> = span.content.chars().collect();
+            let offset = col - start;
+            if offset > 0 {
+                spans.push(Span::styled(
+                    chars[..offset].iter().collect::<String>(),
+                    span.style,
+                ));
+            }
+            spans.push(Span::styled(
+                chars[offset].to_string(),
+                span.style.patch(style),
+            ));
+            if offset + 1 < len {
+                spans.push(Span::styled(
+                    chars[offset + 1..].iter().collect::<String>(),
+                    span.style,
+                ));
+            }
+        } else {
+            spans.push(span);
+        }
+        start += len;
+    }
+    Line::from(spans)
+}
+
+fn draw_request_auth(frame: &mut Frame, app: &App, area: Rect, accent: Color) {
+    if !app.current_request.use_collection_auth {
+        draw_auth(
+            frame,
+            app,
+            area,
+            accent,
+            &app.current_request.auth,
+            Vec::new(),
+        );
+        return;
+    }
+
+    let auth_type = app
+        .current_collection_auth()
+        .map_or(AuthType::None.as_str(), |auth| auth.auth_type.as_str());
+    let header = vec![
+        Line::from(vec![
+            Span::styled(
+                format!("Using collection auth: {}", auth_type),
+                Style::default().fg(accent).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                " (press 'C' to use request auth)",
+                Style::default().fg(Color::DarkGray),
+            ),
+        ]),
+        Line::from(""),
+    ];
+    draw_auth(frame, app, area, accent, &app.current_request.auth, header);
+}
+
+fn draw_collection_auth(frame: &mut Frame, app: &App, area: Rect, accent: Color) {
+    let collection = app
+        .collection_auth_index
+        .and_then(|index| app.collections.get(index));
+    let default_auth = AuthConfig::default();
+    let auth = collection
+        .and_then(|collection| collection.default_auth.as_ref())
+        .unwrap_or(&default_auth);
+
+    let header = vec![
+        Line::from(vec![
+            Span::styled("Collection: ", Style::default().fg(Color::DarkGray)),
+            Span::styled(
+                collection.map_or("", |collection| collection.name.as_str()),
+                Style::default().fg(app.theme_text_color()),
+            ),
+        ]),
+        Line::from(""),
+    ];
+    draw_auth(frame, app, area, accent, auth, header);
+}
+
+/// Draw the auth editor for `auth` below `header`, dimmed when the request inherits its
+/// collection's auth
+fn draw_auth(
+    frame: &mut Frame,
+    app: &App,
+    area: Rect,
+    accent: Color,
+    auth: &AuthConfig,
+    header: Vec<Line>,
+) {
+    let dimmed = app.request_tab == RequestTab::Auth && app.current_request.use_collection_auth;
 
     let mut lines: Vec<Line> = Vec::new();
 
@@ -372,6 +625,17 @@ fn draw_auth(frame: &mut Frame, app: &App, area: Rect, accent: Color) {
                 spans.push(Span::styled(&auth.bearer_token, Style::default()));
             }
             lines.push(Line::from(spans));
+
+            if app.show_jwt_decoded {
+                lines.push(Line::from(""));
+                let token = app.interpolate(&auth.bearer_token);
+                lines.extend(jwt_lines(app, &token, accent));
+            } else if !auth.bearer_token.is_empty() {
+                lines.push(Line::from(Span::styled(
+                    "(press 'd' to decode JWT)",
+                    Style::default().fg(Color::DarkGray),
+                )));
+            }
         }
         AuthType::Basic => {
             let is_editing_user = app.input_mode == InputMode::Editing
@@ -485,14 +749,233 @@ fn draw_auth(frame: &mut Frame, app: &App, area: Rect, accent: Color) {
                     },
                     Style::default().fg(accent),
                 ),
+                Span::styled(
+                    "  (press L to toggle)",
+                    Style::default().fg(Color::DarkGray),
+                ),
             ]));
         }
+        AuthType::OAuth2Pkce => {
+            for field in OAuth2Field::ALL {
+                let is_editing = app.input_mode == InputMode::Editing
+                    && app.editing_field == Some(EditingField::AuthOAuth2(field));
+                let placeholder = match field {
+                    OAuth2Field::AuthUrl => "https://auth.example.com/authorize",
+                    OAuth2Field::TokenUrl => "https://auth.example.com/token",
+                    OAuth2Field::ClientId => "Enter client ID...",
+                    OAuth2Field::Scope => "e.g., openid profile",
+                    OAuth2Field::RedirectUri => crate::oauth2::DEFAULT_REDIRECT_URI,
+                };
+                let mut spans = vec![Span::styled(
+                    field.label(),
+                    Style::default().fg(Color::DarkGray),
+                )];
+                spans.extend(text_with_cursor_and_selection(
+                    auth.oauth2.get(field),
+                    app.cursor_position,
+                    is_editing,
+                    placeholder,
+                    Style::default(),
+                    if is_editing {
+                        app.get_selection_range()
+                    } else {
+                        None
+                    },
+                ));
+                lines.push(Line::from(spans));
+            }
+
+            lines.push(Line::from(""));
+            let token = &auth.oauth2.access_token;
+            let mut token_spans = vec![Span::styled(
+                "Access token: ",
+                Style::default().fg(Color::DarkGray),
+            )];
+            if app.is_oauth2_pending() {
+                token_spans.push(Span::styled(
+                    format!("Waiting for browser authorization {}", app.spinner_frame()),
+                    Style::default().fg(Color::Yellow),
+                ));
+            } else if token.is_empty() {
+                token_spans.push(Span::styled(
+                    "none (press 'o' to authorize in the browser)",
+                    Style::default().fg(Color::DarkGray),
+                ));
+            } else {
+                let shown: String = token.chars().take(40).collect();
+                let ellipsis = if token.chars().count() > 40 {
+                    "…"
+                } else {
+                    ""
+                };
+                token_spans.push(Span::styled(
+                    format!("{}{}", shown, ellipsis),
+                    Style::default().fg(accent),
+                ));
+                token_spans.push(Span::styled(
+                    "  (press 'o' to authorize again)",
+                    Style::default().fg(Color::DarkGray),
+                ));
+            }
+            lines.push(Line::from(token_spans));
+        }
     }
 
-    let para = Paragraph::new(lines);
+    if dimmed {
+        lines = lines
+            .into_iter()
+            .map(|line| line.patch_style(Style::default().add_modifier(Modifier::DIM)))
+            .collect();
+    }
+
+    let para = Paragraph::new(header.into_iter().chain(lines).collect::<Vec<_>>());
     frame.render_widget(para, area);
 }
 
+/// Build the decoded JWT section shown below the Bearer token
+fn jwt_lines(app: &App, token: &str, accent: Color) -> Vec<Line<'static>> {
+    let Some(decoded) = decode_jwt(token) else {
+        return vec![Line::from(Span::styled(
+            "Not a valid JWT",
+            Style::default().fg(app.theme_muted_color()),
+        ))];
+    };
+
+    let mut lines = Vec::new();
+    let expired = decoded.is_expired();
+    let expires_at = decoded.expires_at();
+
+    for (title, value) in [("Header", &decoded.header), ("Payload", &decoded.payload)] {
+        lines.push(Line::from(Span::styled(
+            format!("── JWT {} ──", title),
+            Style::default().fg(accent).add_modifier(Modifier::BOLD),
+        )));
+        let pretty = serde_json::to_string_pretty(value).unwrap_or_else(|_| value.to_string());
+        for line in pretty.lines() {
+            let trimmed = line.trim_start();
+            let style = if trimmed.starts_with("\"iss\":") {
+                Style::default().fg(Color::Cyan)
+            } else if trimmed.starts_with("\"sub\":") {
+                Style::default().fg(Color::Yellow)
+            } else if trimmed.starts_with("\"exp\":") {
+                Style::default().fg(if expired { Color::Red } else { Color::Green })
+            } else {
+                Style::default().fg(app.theme_text_color())
+            };
+
+            let mut spans = vec![Span::styled(line.to_string(), style)];
+            if trimmed.starts_with("\"exp\":") {
+                if let Some(exp) = expires_at {
+                    let label = if expired { "expired" } else { "expires" };
+                    spans.push(Span::styled(
+                        format!("  ({} {})", label, exp.format("%Y-%m-%d %H:%M:%S UTC")),
+                        style,
+                    ));
+                }
+            }
+            lines.push(Line::from(spans));
+        }
+    }
+
+    lines
+}
+
+fn draw_settings(frame: &mut Frame, app: &App, area: Rect) {
+    let is_editing = app.input_mode == InputMode::Editing
+        && app.editing_field == Some(EditingField::ProxyOverride);
+    let proxy = app.current_request.proxy_override.as_deref().unwrap_or("");
+
+    let mut spans = vec![Span::styled(
+        "Proxy: ",
+        Style::default().fg(Color::DarkGray),
+    )];
+    if is_editing {
+        spans.extend(text_with_cursor_and_selection(
+            proxy,
+            app.cursor_position,
+            true,
+            "http://proxy:8080",
+            Style::default(),
+            app.get_selection_range(),
+        ));
+    } else if proxy.is_empty() {
+        spans.push(Span::styled(
+            "Global settings",
+            Style::default().fg(Color::DarkGray),
+        ));
+    } else {
+        spans.push(Span::styled(proxy, Style::default()));
+    }
+
+    let schema_editing = app.input_mode == InputMode::Editing
+        && app.editing_field == Some(EditingField::ResponseSchema);
+    let schema = app.current_request.response_schema.as_deref().unwrap_or("");
+    let mut schema_spans = vec![Span::styled(
+        "Response schema: ",
+        Style::default().fg(Color::DarkGray),
+    )];
+    if schema_editing {
+        schema_spans.extend(text_with_cursor_and_selection(
+            schema,
+            app.cursor_position,
+            true,
+            r#"{"type": "object"}"#,
+            Style::default(),
+            app.get_selection_range(),
+        ));
+    } else if schema.is_empty() {
+        schema_spans.push(Span::styled("None", Style::default().fg(Color::DarkGray)));
+    } else {
+        schema_spans.push(Span::styled(schema, Style::default()));
+    }
+
+    let description_editing = app.input_mode == InputMode::Editing
+        && app.editing_field == Some(EditingField::Description);
+    let description = app.current_request.description.as_deref().unwrap_or("");
+    let mut description_spans = vec![Span::styled(
+        "Description: ",
+        Style::default().fg(Color::DarkGray),
+    )];
+    if description_editing {
+        description_spans.extend(text_with_cursor_and_selection(
+            description,
+            app.cursor_position,
+            true,
+            "What this request is for",
+            Style::default(),
+            app.get_selection_range(),
+        ));
+    } else if description.is_empty() {
+        description_spans.push(Span::styled("None", Style::default().fg(Color::DarkGray)));
+    } else {
+        description_spans.push(Span::styled(description, Style::default()));
+    }
+
+    let lines = vec![
+        Line::from(spans),
+        Line::from(schema_spans),
+        Line::from(description_spans),
+        Line::from(""),
+        Line::from(Span::styled(
+            "Press Enter to edit, Tab to switch field. Leave the proxy empty to use the global settings, or enter 'none' to bypass proxies.",
+            Style::default().fg(app.theme_muted_color()),
+        )),
+        Line::from(Span::styled(
+            "The response schema is JSON Schema; press V in the response view to check a response against it.",
+            Style::default().fg(app.theme_muted_color()),
+        )),
+        Line::from(Span::styled(
+            "The description is shown in the footer when hovering over the request in the list.",
+            Style::default().fg(app.theme_muted_color()),
+        )),
+    ];
+
+    frame.render_widget(
+        Paragraph::new(lines).wrap(ratatui::widgets::Wrap { trim: false }),
+        area,
+    );
+}
+
 fn draw_params(frame: &mut Frame, app: &App, area: Rect, accent: Color) {
     let mut lines: Vec<Line> = Vec::new();
     let is_focused = app.focused_panel == FocusedPanel::RequestEditor
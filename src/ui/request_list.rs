@@ -4,18 +4,20 @@ use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{List, ListItem, Paragraph},
+    widgets::{List, ListItem, ListState, Paragraph},
     Frame,
 };
 
 use super::layout::bordered_block_with_number;
-use super::widgets::text_with_cursor;
+use super::widgets::{format_size, text_with_cursor};
 
 pub fn draw(frame: &mut Frame, app: &App, area: Rect) {
     let focused = app.focused_panel == FocusedPanel::RequestList;
     let accent = app.accent_color();
     let title = if app.show_history {
         "History"
+    } else if app.show_only_favorites {
+        "Collections [★ favorites]"
     } else {
         "Collections"
     };
@@ -63,6 +65,29 @@ fn draw_search_bar(frame: &mut Frame, app: &App, area: Rect, accent: Color) {
         Style::default().fg(app.theme_muted_color())
     };
 
+    // Match count, e.g. [3/7], right-aligned at the end of the bar
+    let area = if app.has_request_list_filter() {
+        let (position, total) = app.request_list_match_position();
+        let count = format!("[{}/{}]", position, total);
+        let count_width = (count.len() as u16).min(area.width);
+        let count_area = Rect {
+            x: area.x + area.width - count_width,
+            y: area.y,
+            width: count_width,
+            height: 1,
+        };
+        frame.render_widget(
+            Paragraph::new(count).style(Style::default().fg(app.theme_muted_color())),
+            count_area,
+        );
+        Rect {
+            width: area.width - count_width,
+            ..area
+        }
+    } else {
+        area
+    };
+
     if app.request_list_search_active {
         // Show cursor when actively searching
         let mut spans = vec![Span::styled("/ ", search_style)];
@@ -105,6 +130,7 @@ fn draw_collections(frame: &mut Frame, app: &App, area: Rect, accent: Color) {
                             crate::storage::HttpMethod::Put => Color::Blue,
                             crate::storage::HttpMethod::Patch => Color::Magenta,
                             crate::storage::HttpMethod::Delete => Color::Red,
+                            crate::storage::HttpMethod::Head => Color::Cyan,
                         };
 
                         let name_style = if is_selected {
@@ -128,6 +154,9 @@ fn draw_collections(frame: &mut Frame, app: &App, area: Rect, accent: Color) {
                             format!("{} ", req.method.as_str()),
                             Style::default().fg(method_color),
                         )];
+                        if req.favorite {
+                            line_spans.push(Span::styled("★ ", Style::default().fg(Color::Yellow)));
+                        }
                         line_spans.extend(name_spans);
 
                         // Add collection name as context (dimmed)
@@ -155,6 +184,13 @@ fn draw_collections(frame: &mut Frame, app: &App, area: Rect, accent: Color) {
                     .fg(app.theme_selection_fg())
                     .bg(app.theme_selection_bg())
                     .add_modifier(Modifier::BOLD)
+            } else if let Some(color) = &collection.color {
+                let style = Style::default().fg(App::parse_color_pub(color));
+                if col_idx == app.selected_collection {
+                    style.add_modifier(Modifier::BOLD)
+                } else {
+                    style
+                }
             } else if col_idx == app.selected_collection {
                 Style::default().fg(accent).add_modifier(Modifier::BOLD)
             } else {
@@ -173,6 +209,7 @@ fn draw_collections(frame: &mut Frame, app: &App, area: Rect, accent: Color) {
                         && item_idx == app.selected_item;
 
                     let indent = "  ".repeat(depth + 1);
+                    let favorite = matches!(item, CollectionItem::Request(req) if req.favorite);
                     let (icon, name, method_style) = match item {
                         CollectionItem::Request(req) => {
                             let method_color = match req.method {
@@ -181,6 +218,7 @@ fn draw_collections(frame: &mut Frame, app: &App, area: Rect, accent: Color) {
                                 crate::storage::HttpMethod::Put => Color::Blue,
                                 crate::storage::HttpMethod::Patch => Color::Magenta,
                                 crate::storage::HttpMethod::Delete => Color::Red,
+                                crate::storage::HttpMethod::Head => Color::Cyan,
                             };
                             (
                                 format!("{} ", req.method.as_str()),
@@ -203,11 +241,12 @@ fn draw_collections(frame: &mut Frame, app: &App, area: Rect, accent: Color) {
                         Style::default().fg(app.theme_text_color())
                     };
 
-                    items.push(ListItem::new(Line::from(vec![
-                        Span::raw(indent),
-                        Span::styled(icon, method_style),
-                        Span::styled(name, name_style),
-                    ])));
+                    let mut spans = vec![Span::raw(indent), Span::styled(icon, method_style)];
+                    if favorite {
+                        spans.push(Span::styled("★ ", Style::default().fg(Color::Yellow)));
+                    }
+                    spans.push(Span::styled(name, name_style));
+                    items.push(ListItem::new(Line::from(spans)));
                 }
             }
         }
@@ -222,6 +261,11 @@ fn draw_collections(frame: &mut Frame, app: &App, area: Rect, accent: Color) {
         let placeholder =
             Paragraph::new(message).style(Style::default().fg(app.theme_muted_color()));
         frame.render_widget(placeholder, area);
+    } else if has_filter {
+        // Keep the selected match scrolled into view
+        let mut state =
+            ListState::default().with_selected(Some(app.request_list_filtered_selection));
+        frame.render_stateful_widget(List::new(items), area, &mut state);
     } else {
         let list = List::new(items);
         frame.render_widget(list, area);
@@ -274,6 +318,7 @@ fn draw_history(frame: &mut Frame, app: &App, area: Rect, _accent: Color) {
                 crate::storage::HttpMethod::Put => Color::Blue,
                 crate::storage::HttpMethod::Patch => Color::Magenta,
                 crate::storage::HttpMethod::Delete => Color::Red,
+                crate::storage::HttpMethod::Head => Color::Cyan,
             };
 
             let status_color = match entry.status_code {
@@ -323,6 +368,37 @@ fn draw_history(frame: &mut Frame, app: &App, area: Rect, _accent: Color) {
                     Style::default().fg(status_color),
                 ),
             ];
+            if app.history_detailed_mode {
+                let url_spans = if has_filter {
+                    highlight_matches(
+                        &entry.request.url,
+                        &app.request_list_search_query,
+                        style,
+                        accent,
+                    )
+                } else {
+                    vec![Span::styled(entry.request.url.clone(), style)]
+                };
+                let timestamp = entry
+                    .timestamp
+                    .with_timezone(&chrono::Local)
+                    .format("%Y-%m-%d %H:%M:%S")
+                    .to_string();
+                spans.push(Span::styled(timestamp, style.fg(app.theme_muted_color())));
+
+                let mut details = format!("  {}ms", entry.duration_ms);
+                if let Some(size) = entry.size_bytes {
+                    details.push_str(&format!(" • {}", format_size(size)));
+                }
+                let mut url_line = vec![Span::styled("  ", style)];
+                url_line.extend(url_spans);
+
+                return ListItem::new(vec![
+                    Line::from(spans),
+                    Line::from(url_line),
+                    Line::from(Span::styled(details, style.fg(app.theme_muted_color()))),
+                ]);
+            }
             spans.extend(path_spans);
 
             ListItem::new(Line::from(spans))
@@ -338,6 +414,14 @@ fn draw_history(frame: &mut Frame, app: &App, area: Rect, _accent: Color) {
         let placeholder =
             Paragraph::new(message).style(Style::default().fg(app.theme_muted_color()));
         frame.render_widget(placeholder, area);
+    } else if app.history_detailed_mode {
+        let offset = app.history_detailed_offset(area.height as usize);
+        let items: Vec<ListItem> = items.into_iter().skip(offset).collect();
+        frame.render_widget(List::new(items), area);
+    } else if has_filter {
+        // Keep the selected match scrolled into view
+        let mut state = ListState::default().with_selected(Some(app.selected_history));
+        frame.render_stateful_widget(List::new(items), area, &mut state);
     } else {
         let list = List::new(items);
         frame.render_widget(list, area);
@@ -345,7 +429,7 @@ fn draw_history(frame: &mut Frame, app: &App, area: Rect, _accent: Color) {
 }
 
 /// Highlight matching parts of text with accent color
-fn highlight_matches(
+pub(super) fn highlight_matches(
     text: &str,
     query: &str,
     base_style: Style,
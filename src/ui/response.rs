@@ -1,16 +1,23 @@
-use crate::app::{App, FocusedPanel, ResponseMode};
+use crate::app::{App, FocusedPanel, PipelineFilterState, ResponseMode, MAX_SUBSCRIPTION_MESSAGES};
+use crate::diff::{self, DiffLine};
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState, Wrap},
+    widgets::{
+        Block, Borders, Clear, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState, Wrap,
+    },
     Frame,
 };
 
 use super::layout::bordered_block_with_number;
-use super::widgets::text_with_cursor_and_selection;
+use super::widgets::{draw_size_gauge, format_size, text_with_cursor_and_selection};
 
-pub fn draw(frame: &mut Frame, app: &App, area: Rect) {
+/// Width of the minimap column beside long responses
+const MINIMAP_WIDTH: u16 = 3;
+
+pub fn draw(frame: &mut Frame, app: &mut App, area: Rect) {
+    app.layout_areas.response_minimap = None;
     let focused = app.focused_panel == FocusedPanel::ResponseView;
     let accent = app.accent_color();
     let block = bordered_block_with_number(
@@ -29,6 +36,12 @@ pub fn draw(frame: &mut Frame, app: &App, area: Rect) {
         return;
     }
 
+    if app.showing_subscription() {
+        draw_subscription(frame, app, inner_area, accent);
+        return;
+    }
+
+    let mut minimap_rect = None;
     match &app.response {
         Some(response) => {
             // Show status bar when: in input mode, have active filter, or have search matches
@@ -36,16 +49,31 @@ pub fn draw(frame: &mut Frame, app: &App, area: Rect) {
                 || app.response_filtered_content.is_some()
                 || !app.response_search_matches.is_empty();
 
-            let constraints = if show_status_bar {
-                vec![
-                    Constraint::Length(2),
-                    Constraint::Min(3),
-                    Constraint::Length(1),
-                ]
+            // Raw headers section in verbose mode (one line when collapsed)
+            let headers_height = if !app.verbose_mode {
+                0
+            } else if app.response_headers_expanded {
+                (response.headers.len() as u16 + 1).min(inner_area.height / 3)
             } else {
-                vec![Constraint::Length(2), Constraint::Min(3)]
+                1
             };
 
+            let mut constraints = vec![
+                Constraint::Length(2),
+                Constraint::Length(headers_height),
+                Constraint::Min(3),
+            ];
+            if show_status_bar {
+                // A pipeline filter takes one row per stage
+                let bar_height = match &app.pipeline_filter {
+                    Some(pipeline) if app.response_mode == ResponseMode::Filter => {
+                        pipeline.stages.len() as u16
+                    }
+                    _ => 1,
+                };
+                constraints.push(Constraint::Length(bar_height));
+            }
+
             let chunks = Layout::default()
                 .direction(Direction::Vertical)
                 .constraints(constraints)
@@ -54,24 +82,157 @@ pub fn draw(frame: &mut Frame, app: &App, area: Rect) {
             // Status line
             draw_status(frame, app, response, chunks[0], accent);
 
-            // Response body with syntax highlighting
-            draw_body(frame, app, chunks[1], accent);
+            if app.verbose_mode {
+                draw_raw_headers(frame, app, response, chunks[1], accent);
+            }
+
+            // Response body with syntax highlighting, and the minimap beside long ones
+            let body_area = chunks[2];
+            let visible_height = body_area.height.saturating_sub(1) as usize;
+            if let Some(diff) = &app.response_diff {
+                draw_diff(frame, app, diff, body_area);
+            } else if app.settings.show_minimap
+                && app.response_line_count() > visible_height * 2
+                && body_area.width > MINIMAP_WIDTH * 4
+            {
+                let [body_area, minimap_area] =
+                    Layout::horizontal([Constraint::Min(1), Constraint::Length(MINIMAP_WIDTH)])
+                        .areas(body_area);
+                draw_body(frame, app, body_area, accent);
+                draw_minimap(frame, app, minimap_area);
+                // Rows below the body's top border line up with the minimap rows
+                minimap_rect = Some(Rect {
+                    y: minimap_area.y + 1,
+                    height: minimap_area.height.saturating_sub(1),
+                    ..minimap_area
+                });
+            } else {
+                draw_body(frame, app, body_area, accent);
+            }
+            if app.show_timing_breakdown {
+                draw_timing_breakdown(frame, app, response, body_area, accent);
+            }
 
             // Search/filter status bar
             if show_status_bar {
-                draw_search_bar(frame, app, chunks[2], accent);
+                draw_search_bar(frame, app, chunks[3], accent);
             }
         }
         None => {
-            let placeholder = Paragraph::new("No response yet. Send a request with 's'.")
-                .style(Style::default().fg(app.theme_muted_color()));
-            frame.render_widget(placeholder, inner_area);
+            if let Some(readme) = app.collection_readme() {
+                draw_readme(frame, app, readme, inner_area, accent);
+            } else {
+                let placeholder = Paragraph::new("No response yet. Send a request with 's'.")
+                    .style(Style::default().fg(app.theme_muted_color()));
+                frame.render_widget(placeholder, inner_area);
+            }
         }
     }
+    app.layout_areas.response_minimap =
+        minimap_rect.map(|rect: Rect| (rect.x, rect.y, rect.width, rect.height));
+}
+
+/// Horizontal bar per request phase, scaled to the total time
+fn draw_timing_breakdown(
+    frame: &mut Frame,
+    app: &App,
+    response: &crate::http::HttpResponse,
+    area: Rect,
+    accent: Color,
+) {
+    let timing = &response.timing;
+    let colors = [
+        Color::Yellow,
+        Color::Blue,
+        Color::Magenta,
+        Color::Green,
+        Color::Cyan,
+    ];
+    let width = area.width.saturating_sub(4).min(60);
+    let height = 9;
+    if width < 30 || area.height < height {
+        return;
+    }
+    let rect = Rect {
+        x: area.x + area.width - width - 1,
+        y: area.y + 1,
+        width,
+        height,
+    };
+
+    // label (8) + gap + bar + gap + "99999ms" (7), inside the borders
+    let bar_width = width.saturating_sub(2 + 8 + 1 + 1 + 7) as usize;
+    let total = timing.total_ms();
+    let mut lines: Vec<Line> = timing
+        .phases()
+        .iter()
+        .zip(colors)
+        .map(|((label, ms), color)| {
+            Line::from(vec![
+                Span::styled(
+                    format!("{:<8} ", label),
+                    Style::default().fg(app.theme_text_color()),
+                ),
+                Span::styled(
+                    format!("{:<bar_width$}", quarter_bar(*ms, total, bar_width)),
+                    Style::default().fg(color),
+                ),
+                Span::styled(
+                    format!(" {:>5}ms", ms),
+                    Style::default().fg(app.theme_muted_color()),
+                ),
+            ])
+        })
+        .collect();
+    let note = if response.cached {
+        "Served from cache"
+    } else {
+        "DNS, Connect and TLS are 0 on a reused connection"
+    };
+    lines.push(Line::from(Span::styled(
+        note,
+        Style::default().fg(app.theme_muted_color()),
+    )));
+
+    frame.render_widget(Clear, rect);
+    let block = Block::default()
+        .title(format!(" Timing ({}ms) ", total))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(accent))
+        .style(Style::default().bg(app.theme().surface));
+    frame.render_widget(Paragraph::new(lines).block(block), rect);
+}
+
+/// Bar of `value / total` of `width` cells, drawn at quarter-cell resolution
+fn quarter_bar(value: u64, total: u64, width: usize) -> String {
+    if total == 0 {
+        return String::new();
+    }
+    let quarters = ((value as f64 / total as f64) * width as f64 * 4.0).round() as usize;
+    let mut bar = "█".repeat(quarters / 4);
+    match quarters % 4 {
+        1 => bar.push('▎'),
+        2 => bar.push('▌'),
+        3 => bar.push('▊'),
+        _ => {}
+    }
+    bar
 }
 
 fn draw_loading(frame: &mut Frame, app: &App, area: Rect) {
-    let loading = Paragraph::new(format!("Sending request {}", app.spinner_frame())).style(
+    let text = match app.request_progress {
+        Some(progress) => {
+            let filled = (progress * 10.0).round() as usize;
+            format!(
+                "Receiving response [{}{} {:>3}%]",
+                "█".repeat(filled),
+                "░".repeat(10 - filled.min(10)),
+                (progress * 100.0).round() as u32
+            )
+        }
+        None => format!("Sending request {}", app.spinner_frame()),
+    };
+    let loading = Paragraph::new(text).style(
         Style::default()
             .fg(Color::Yellow)
             .add_modifier(Modifier::BOLD),
@@ -94,7 +255,7 @@ fn draw_status(
         Color::Yellow
     };
 
-    let status_line = Line::from(vec![
+    let mut status_line = Line::from(vec![
         Span::styled(
             format!(" {} {} ", response.status, response.status_text),
             Style::default()
@@ -108,16 +269,276 @@ fn draw_status(
             Style::default().fg(accent),
         ),
         Span::raw("  "),
-        Span::styled(
+    ]);
+    // Past half the warning threshold, show a gauge instead of the plain size
+    let threshold = app.settings.response_size_warning_bytes;
+    if threshold > 0 && response.size_bytes * 2 > threshold {
+        for span in draw_size_gauge(response.size_bytes, threshold, 8) {
+            status_line.push_span(span);
+        }
+    } else {
+        status_line.push_span(Span::styled(
             format_size(response.size_bytes),
             Style::default().fg(app.theme_muted_color()),
-        ),
-    ]);
+        ));
+    }
+    if response.cached {
+        status_line.push_span(Span::raw("  "));
+        status_line.push_span(Span::styled("[cached]", Style::default().fg(Color::Cyan)));
+    }
+    if response.is_head {
+        status_line.push_span(Span::raw("  "));
+        status_line.push_span(Span::styled("(HEAD)", Style::default().fg(Color::Cyan)));
+    }
+    if let Some(encoding) = app.response_encoding {
+        status_line.push_span(Span::raw("  "));
+        status_line.push_span(Span::styled(
+            format!("[{}]", encoding.label()),
+            Style::default().fg(Color::Cyan),
+        ));
+    } else if let Some(charset) = response
+        .detected_charset
+        .as_deref()
+        .filter(|charset| !matches!(*charset, "utf-8" | "utf8"))
+    {
+        status_line.push_span(Span::raw("  "));
+        status_line.push_span(Span::styled(
+            format!("charset: {}", charset),
+            Style::default().fg(Color::Yellow),
+        ));
+    }
+    if !app.response_hex_view {
+        status_line.push_span(Span::raw("  "));
+        status_line.push_span(Span::styled(
+            format!("[{}]", app.response_display_mode.as_str()),
+            Style::default().fg(Color::Cyan),
+        ));
+    }
+    let note = if app.response_hex_view {
+        Some("[hex]")
+    } else if app.is_response_raw() {
+        None
+    } else if app.response_browse_mode && response.is_html() {
+        Some("[HTML→text]")
+    } else if response.decoded_msgpack {
+        Some("[msgpack → json]")
+    } else if app.is_response_jwt_decoded() {
+        Some("[JWT]")
+    } else {
+        None
+    };
+    if let Some(note) = note {
+        status_line.push_span(Span::raw("  "));
+        status_line.push_span(Span::styled(note, Style::default().fg(Color::Cyan)));
+    }
+    if app.response_diff.is_some() {
+        status_line.push_span(Span::raw("  "));
+        status_line.push_span(Span::styled("[diff]", Style::default().fg(Color::Magenta)));
+    }
+    if app.response_history_index > 0 {
+        status_line.push_span(Span::raw("  "));
+        status_line.push_span(Span::styled(
+            format!("Viewing history [-{}]", app.response_history_index),
+            Style::default().fg(Color::Magenta),
+        ));
+    }
+    if let Some(errors) = &app.last_validation_errors {
+        let (badge, color) = if errors.is_empty() {
+            ("Schema valid ✓".to_string(), Color::Green)
+        } else {
+            (format!("Schema errors: {}", errors.len()), Color::Red)
+        };
+        status_line.push_span(Span::raw("  "));
+        status_line.push_span(Span::styled(badge, Style::default().fg(color)));
+    }
 
     let para = Paragraph::new(status_line);
     frame.render_widget(para, area);
 }
 
+/// Messages of the GraphQL subscription, newest at the bottom and kept in view
+fn draw_subscription(frame: &mut Frame, app: &App, area: Rect, accent: Color) {
+    let [status_area, body_area] =
+        Layout::vertical([Constraint::Length(2), Constraint::Min(1)]).areas(area);
+
+    let muted = Style::default().fg(app.theme_muted_color());
+    let mut status = Line::default();
+    if app.current_subscription.is_some() {
+        status.push_span(Span::styled(
+            "[SUBSCRIBED]",
+            Style::default()
+                .fg(Color::Black)
+                .bg(Color::Green)
+                .add_modifier(Modifier::BOLD),
+        ));
+    } else {
+        status.push_span(Span::styled("[subscription ended]", muted));
+    }
+    status.push_span(Span::raw("  "));
+    status.push_span(Span::styled(
+        format!(
+            "{} messages (last {} kept)",
+            app.subscription_messages.len(),
+            MAX_SUBSCRIPTION_MESSAGES
+        ),
+        Style::default().fg(accent),
+    ));
+    if app.current_subscription.is_some() {
+        status.push_span(Span::styled("  S in the request editor stops", muted));
+    }
+    frame.render_widget(Paragraph::new(status), status_area);
+
+    if app.subscription_messages.is_empty() {
+        frame.render_widget(
+            Paragraph::new("Waiting for messages...").style(muted),
+            body_area,
+        );
+        return;
+    }
+    let mut lines: Vec<Line> = Vec::new();
+    for (index, message) in app.subscription_messages.iter().enumerate() {
+        lines.push(Line::from(Span::styled(
+            format!("── #{} ──", index + 1),
+            muted,
+        )));
+        lines.extend(message.lines().map(highlight_json_line).map(Line::from));
+    }
+    let scroll = lines.len().saturating_sub(body_area.height as usize) as u16;
+    frame.render_widget(Paragraph::new(lines).scroll((scroll, 0)), body_area);
+}
+
+/// Collection README as plain text, with Markdown `#` headings in the accent color
+fn draw_readme(frame: &mut Frame, app: &App, readme: &str, area: Rect, accent: Color) {
+    let lines: Vec<Line> = readme
+        .lines()
+        .map(|line| {
+            if line.trim_start().starts_with('#') {
+                Line::from(Span::styled(
+                    line.to_string(),
+                    Style::default().fg(accent).add_modifier(Modifier::BOLD),
+                ))
+            } else {
+                Line::from(Span::styled(
+                    line.to_string(),
+                    Style::default().fg(app.theme_text_color()),
+                ))
+            }
+        })
+        .collect();
+    frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), area);
+}
+
+fn draw_raw_headers(
+    frame: &mut Frame,
+    app: &App,
+    response: &crate::http::HttpResponse,
+    area: Rect,
+    accent: Color,
+) {
+    let marker = if app.response_headers_expanded {
+        "▼"
+    } else {
+        "▶"
+    };
+    let mut lines = vec![Line::from(vec![
+        Span::styled(
+            format!("{} Headers ({})", marker, response.headers.len()),
+            Style::default().fg(accent).add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(
+            "  v to toggle",
+            Style::default().fg(app.theme_muted_color()),
+        ),
+    ])];
+
+    if app.response_headers_expanded {
+        for (key, value) in &response.headers {
+            lines.push(Line::from(vec![
+                Span::styled(format!("< {}: ", key), Style::default().fg(Color::Cyan)),
+                Span::styled(value.clone(), Style::default().fg(app.theme_text_color())),
+            ]));
+        }
+    }
+
+    frame.render_widget(Paragraph::new(lines), area);
+}
+
+/// Snapshot (left) against the current response (right), removed lines in red and
+/// added lines in green
+fn draw_diff(frame: &mut Frame, app: &App, diff: &[DiffLine], area: Rect) {
+    let block = Block::default()
+        .borders(Borders::TOP)
+        .title(" snapshot │ current ")
+        .border_style(Style::default().fg(app.theme_muted_color()))
+        .style(Style::default().bg(app.theme_surface_color()));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let [left_area, divider_area, right_area] = Layout::horizontal([
+        Constraint::Fill(1),
+        Constraint::Length(1),
+        Constraint::Fill(1),
+    ])
+    .areas(inner);
+
+    let rows = diff::side_by_side(diff);
+    let side = |line: Option<&DiffLine>| match line {
+        Some(DiffLine::Same(text)) => Line::from(Span::styled(
+            format!("  {}", text),
+            Style::default().fg(app.theme_text_color()),
+        )),
+        Some(DiffLine::Removed(text)) => Line::from(Span::styled(
+            format!("- {}", text),
+            Style::default().fg(Color::Red),
+        )),
+        Some(DiffLine::Added(text)) => Line::from(Span::styled(
+            format!("+ {}", text),
+            Style::default().fg(Color::Green),
+        )),
+        None => Line::from(""),
+    };
+    let visible = rows
+        .iter()
+        .skip(app.response_scroll as usize)
+        .take(inner.height as usize);
+    let (left, right): (Vec<Line>, Vec<Line>) =
+        visible.map(|(old, new)| (side(*old), side(*new))).unzip();
+
+    frame.render_widget(Paragraph::new(left), left_area);
+    frame.render_widget(
+        Paragraph::new(vec![Line::from("│"); inner.height as usize])
+            .style(Style::default().fg(app.theme_muted_color())),
+        divider_area,
+    );
+    frame.render_widget(Paragraph::new(right), right_area);
+}
+
+/// A line of a decoded JWT body: section separators in the accent color and the
+/// `exp` claim green or red depending on whether it has passed
+fn jwt_line(line: &str, expired: bool, accent: Color) -> Vec<Span<'static>> {
+    if matches!(line, "HEADER:" | "PAYLOAD:") {
+        return vec![Span::styled(
+            line.to_string(),
+            Style::default().fg(accent).add_modifier(Modifier::BOLD),
+        )];
+    }
+    if line.trim_start().starts_with("\"exp\":") {
+        let (color, note) = if expired {
+            (Color::Red, "  (expired)")
+        } else {
+            (Color::Green, "  (valid)")
+        };
+        return vec![
+            Span::styled(line.to_string(), Style::default().fg(color)),
+            Span::styled(
+                note,
+                Style::default().fg(color).add_modifier(Modifier::ITALIC),
+            ),
+        ];
+    }
+    highlight_json_line(line)
+}
+
 fn draw_body(frame: &mut Frame, app: &App, area: Rect, accent: Color) {
     // Get content source - use filtered if available, otherwise cached lines
     let (content_lines, total_lines): (Vec<&str>, usize) =
@@ -126,7 +547,11 @@ fn draw_body(frame: &mut Frame, app: &App, area: Rect, accent: Color) {
             let count = lines.len();
             (lines, count)
         } else {
-            let lines: Vec<&str> = app.response_lines.iter().map(|s| s.as_str()).collect();
+            let lines: Vec<&str> = app
+                .response_display_lines()
+                .iter()
+                .map(|s| s.as_str())
+                .collect();
             let count = lines.len();
             (lines, count)
         };
@@ -139,6 +564,9 @@ fn draw_body(frame: &mut Frame, app: &App, area: Rect, accent: Color) {
     let end_line = (scroll_pos + visible_height + 1).min(total_lines); // +1 for partial lines
 
     let search_query = app.response_search_query.to_lowercase();
+    let gutter_width = total_lines.to_string().len();
+    let jwt_decoded = app.is_response_jwt_decoded();
+    let jwt_expired = jwt_decoded && app.response_jwt_expired();
 
     // Only process visible lines - this is the key optimization
     let lines: Vec<Line> = content_lines
@@ -154,11 +582,22 @@ fn draw_body(frame: &mut Frame, app: &App, area: Rect, accent: Color) {
             // Basic JSON syntax highlighting - only for visible lines
             let styled_line = if is_match && !search_query.is_empty() {
                 highlight_json_line_with_search(line, &search_query, accent)
+            } else if jwt_decoded {
+                jwt_line(line, jwt_expired, accent)
             } else {
                 highlight_json_line(line)
             };
 
-            let line = Line::from(styled_line);
+            let line = if app.settings.show_line_numbers {
+                let mut spans = vec![Span::styled(
+                    format!("{:>width$} ", line_num + 1, width = gutter_width),
+                    Style::default().fg(app.theme_muted_color()),
+                )];
+                spans.extend(styled_line);
+                Line::from(spans)
+            } else {
+                Line::from(styled_line)
+            };
 
             // Add background for current match
             if is_current_match {
@@ -193,9 +632,151 @@ fn draw_body(frame: &mut Frame, app: &App, area: Rect, accent: Color) {
 
         frame.render_stateful_widget(scrollbar, area, &mut scrollbar_state);
     }
+
+    // Line position on the top border, one cell left of the scrollbar column
+    if total_lines > 0 {
+        let mut indicator = format!(
+            " Line {}/{} ",
+            (scroll_pos + 1).min(total_lines),
+            total_lines
+        );
+        if app.response_filtered_content.is_some() {
+            indicator.push_str("(filtered) ");
+        }
+        let width = (indicator.chars().count() as u16).min(area.width.saturating_sub(1));
+        let indicator_area = Rect {
+            x: area.x + area.width.saturating_sub(width + 1),
+            y: area.y,
+            width,
+            height: 1,
+        };
+        frame.render_widget(
+            Paragraph::new(Span::styled(
+                indicator,
+                Style::default().fg(app.theme_muted_color()),
+            )),
+            indicator_area,
+        );
+    }
+}
+
+/// Proportional overview of the whole response: one row per slice of lines,
+/// colored by what the slice holds, with the viewport shown as a band
+fn draw_minimap(frame: &mut Frame, app: &App, area: Rect) {
+    let content_lines: Vec<&str> = match &app.response_filtered_content {
+        Some(filtered) => filtered.lines().collect(),
+        None => app
+            .response_display_lines()
+            .iter()
+            .map(|s| s.as_str())
+            .collect(),
+    };
+    let total = content_lines.len();
+    // Leave the top row for the body's border line
+    let rows = area.height.saturating_sub(1) as usize;
+    if total == 0 || rows == 0 {
+        return;
+    }
+
+    let visible_height = rows.max(1);
+    let viewport_start = app.response_scroll as usize;
+    let viewport_end = viewport_start + visible_height;
+    let longest = content_lines
+        .iter()
+        .map(|line| line.chars().count())
+        .max()
+        .unwrap_or(1)
+        .max(1);
+
+    let lines: Vec<Line> = (0..rows)
+        .map(|row| {
+            let start = row * total / rows;
+            let end = ((row + 1) * total / rows).max(start + 1).min(total);
+            let slice = &content_lines[start.min(total - 1)..end];
+
+            let color = minimap_color(slice, app.theme_text_color());
+            let width = slice
+                .iter()
+                .map(|line| line.chars().count())
+                .max()
+                .unwrap_or(0);
+            let blocks = (width * MINIMAP_WIDTH as usize)
+                .div_ceil(longest)
+                .clamp(1, MINIMAP_WIDTH as usize);
+            let text = format!(
+                "{:<width$}",
+                "▀".repeat(blocks),
+                width = MINIMAP_WIDTH as usize
+            );
+
+            let mut style = Style::default().fg(color);
+            if start < viewport_end && end > viewport_start {
+                style = style.bg(app.theme_selection_bg());
+            }
+            Line::from(Span::styled(text, style))
+        })
+        .collect();
+
+    // Continue the body's top border over the minimap column
+    frame.render_widget(
+        Paragraph::new(Span::styled(
+            "─".repeat(area.width as usize),
+            Style::default().fg(app.theme_muted_color()),
+        )),
+        Rect { height: 1, ..area },
+    );
+
+    let minimap_area = Rect {
+        y: area.y + 1,
+        height: area.height.saturating_sub(1),
+        ..area
+    };
+    frame.render_widget(
+        Paragraph::new(lines).style(Style::default().bg(app.theme_surface_color())),
+        minimap_area,
+    );
+}
+
+/// Red for error-like content, then green for strings, yellow for numbers,
+/// the text color for structure only
+fn minimap_color(lines: &[&str], structure: Color) -> Color {
+    let lowered: Vec<String> = lines.iter().map(|line| line.to_lowercase()).collect();
+    if lowered
+        .iter()
+        .any(|line| line.contains("error") || line.contains("exception") || line.contains("fail"))
+    {
+        return Color::Red;
+    }
+    let values = lines.iter().map(|line| {
+        line.split_once(": ")
+            .map_or(line.trim(), |(_, value)| value.trim())
+    });
+    let mut has_number = false;
+    for value in values {
+        let value = value.trim_end_matches(',');
+        if value.starts_with('"') {
+            return Color::Green;
+        }
+        if value.parse::<f64>().is_ok() {
+            has_number = true;
+        }
+    }
+    if has_number {
+        Color::Yellow
+    } else {
+        structure
+    }
 }
 
 fn draw_search_bar(frame: &mut Frame, app: &App, area: Rect, accent: Color) {
+    if let Some(pipeline) = app
+        .pipeline_filter
+        .as_ref()
+        .filter(|_| app.response_mode == ResponseMode::Filter)
+    {
+        draw_pipeline_bar(frame, app, pipeline, area, accent);
+        return;
+    }
     let is_input_mode = app.response_mode != ResponseMode::Normal;
 
     let mut spans = Vec::new();
@@ -213,6 +794,11 @@ fn draw_search_bar(frame: &mut Frame, app: &App, area: Rect, accent: Color) {
                 &app.response_filter_query,
                 app.response_cursor_position,
             ),
+            ResponseMode::GotoLine => (
+                "Go to line: ",
+                &app.goto_line_query,
+                app.goto_line_query.len(),
+            ),
             ResponseMode::Normal => unreachable!(),
         };
 
@@ -232,14 +818,30 @@ fn draw_search_bar(frame: &mut Frame, app: &App, area: Rect, accent: Color) {
     } else {
         // Normal mode - show applied filter/search info
         if app.response_filtered_content.is_some() {
+            // A star marks a filter pinned to every response (Ctrl+Shift+F)
+            let pinned = if app.settings.persistent_filter.is_some() {
+                " | ★"
+            } else {
+                ""
+            };
             spans.push(Span::styled(
-                "jq: ",
+                "[filter: ",
                 Style::default().fg(accent).add_modifier(Modifier::BOLD),
             ));
             spans.push(Span::styled(
                 app.response_filter_query.clone(),
                 Style::default().fg(Color::White),
             ));
+            if let Some(preset) = app.active_jq_preset() {
+                spans.push(Span::styled(
+                    format!(" ({})", preset.name),
+                    Style::default().fg(app.theme_muted_color()),
+                ));
+            }
+            spans.push(Span::styled(
+                format!("{}]", pinned),
+                Style::default().fg(accent).add_modifier(Modifier::BOLD),
+            ));
         } else if !app.response_search_matches.is_empty() {
             spans.push(Span::styled(
                 "/",
@@ -285,6 +887,45 @@ fn draw_search_bar(frame: &mut Frame, app: &App, area: Rect, accent: Color) {
 }
 
 /// Basic JSON syntax highlighting
+/// Filter bar with one row per `|` stage and a preview of each stage's output
+fn draw_pipeline_bar(
+    frame: &mut Frame,
+    app: &App,
+    pipeline: &PipelineFilterState,
+    area: Rect,
+    accent: Color,
+) {
+    let lines: Vec<Line> = pipeline
+        .stages
+        .iter()
+        .enumerate()
+        .map(|(idx, stage)| {
+            let is_active = idx == pipeline.active_stage;
+            let prefix = if idx == 0 { "jq: " } else { "  | " };
+            let mut spans = vec![Span::styled(
+                prefix,
+                Style::default().fg(accent).add_modifier(Modifier::BOLD),
+            )];
+            spans.extend(text_with_cursor_and_selection(
+                stage,
+                app.response_cursor_position,
+                is_active,
+                "",
+                Style::default().fg(Color::White),
+                None,
+            ));
+            if let Some(preview) = pipeline.previews.get(idx).filter(|p| !p.is_empty()) {
+                spans.push(Span::styled(
+                    format!("  → {}", preview),
+                    Style::default().fg(app.theme_muted_color()),
+                ));
+            }
+            Line::from(spans)
+        })
+        .collect();
+    frame.render_widget(Paragraph::new(lines), area);
+}
+
 fn highlight_json_line(line: &str) -> Vec<Span<'static>> {
     let mut spans = Vec::new();
     let trimmed = line.trim_start();
@@ -455,13 +1096,3 @@ fn highlight_json_line_with_search(line: &str, search: &str, accent: Color) -> V
 
     spans
 }
-
-fn format_size(bytes: usize) -> String {
-    if bytes < 1024 {
-        format!("{} B", bytes)
-    } else if bytes < 1024 * 1024 {
-        format!("{:.1} KB", bytes as f64 / 1024.0)
-    } else {
-        format!("{:.1} MB", bytes as f64 / (1024.0 * 1024.0))
-    }
-}
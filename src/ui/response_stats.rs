@@ -0,0 +1,115 @@
+use crate::app::App;
+use crate::stats::ResponseStats;
+use ratatui::{
+    layout::{Alignment, Constraint, Flex, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+pub fn draw_response_stats(frame: &mut Frame, app: &App, stats: &ResponseStats) {
+    let accent = app.accent_color();
+    let theme = app.theme();
+    let heading = |text: &str| {
+        Line::from(Span::styled(
+            text.to_string(),
+            Style::default().fg(accent).add_modifier(Modifier::BOLD),
+        ))
+    };
+    let count_line = |label: &str, count: usize, width: usize| {
+        Line::from(vec![
+            Span::styled(
+                format!("  {:<width$}", label, width = width),
+                Style::default().fg(app.theme_text_color()),
+            ),
+            Span::styled(format!("{:>6}", count), Style::default().fg(Color::Cyan)),
+        ])
+    };
+    let counts_lines = |counts: &[(String, usize)]| -> Vec<Line> {
+        let width = counts
+            .iter()
+            .map(|(key, _)| key.chars().count())
+            .max()
+            .unwrap_or(0)
+            .clamp(8, 40);
+        counts
+            .iter()
+            .map(|(key, count)| count_line(key, *count, width))
+            .collect()
+    };
+
+    let mut lines = Vec::new();
+    let title = match stats {
+        ResponseStats::Json(stats) => {
+            lines.push(heading("Top keys"));
+            if stats.top_keys.is_empty() {
+                lines.push(Line::from(Span::styled(
+                    "  (no object keys)",
+                    Style::default().fg(app.theme_muted_color()),
+                )));
+            }
+            lines.extend(counts_lines(&stats.top_keys));
+            lines.push(Line::from(""));
+            lines.push(heading("Values"));
+            for (label, count) in [
+                ("objects", stats.objects),
+                ("arrays", stats.arrays),
+                ("strings", stats.strings),
+                ("numbers", stats.numbers),
+                ("booleans", stats.booleans),
+                ("nulls", stats.nulls),
+            ] {
+                lines.push(count_line(label, count, 8));
+            }
+            " Response Summary (JSON) "
+        }
+        ResponseStats::Words(words) => {
+            lines.push(heading("Top words"));
+            if words.is_empty() {
+                lines.push(Line::from(Span::styled(
+                    "  (empty body)",
+                    Style::default().fg(app.theme_muted_color()),
+                )));
+            }
+            lines.extend(counts_lines(words));
+            " Response Summary (text) "
+        }
+    };
+
+    let content_width = lines.iter().map(Line::width).max().unwrap_or(0) as u16;
+    let popup_width = (content_width + 4).clamp(36, frame.area().width.max(36));
+    let popup_height = (lines.len() as u16 + 3).clamp(6, frame.area().height.max(6));
+    let area = centered_rect(popup_width, popup_height, frame.area());
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(title)
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(accent))
+        .style(Style::default().bg(theme.surface));
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+
+    let footer_area = Rect {
+        x: area.x,
+        y: area.y + area.height - 1,
+        width: area.width,
+        height: 1,
+    };
+    let footer = Paragraph::new(Line::from(vec![Span::styled(
+        " c copy as JSON • K/Esc close ",
+        Style::default().fg(app.theme_muted_color()),
+    )]))
+    .alignment(Alignment::Center);
+    frame.render_widget(footer, footer_area);
+}
+
+fn centered_rect(width: u16, height: u16, area: Rect) -> Rect {
+    let horizontal = Layout::horizontal([Constraint::Length(width)]).flex(Flex::Center);
+    let vertical = Layout::vertical([Constraint::Length(height)]).flex(Flex::Center);
+
+    let [area] = vertical.areas(area);
+    let [area] = horizontal.areas(area);
+    area
+}
@@ -0,0 +1,106 @@
+use super::widgets::text_with_cursor_and_selection;
+use crate::app::{App, EditingField, InputMode, SettingKey};
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+pub fn draw_settings_popup(frame: &mut Frame, app: &App) {
+    let accent = app.accent_color();
+    let theme = app.theme();
+    let popup = &app.settings_popup;
+
+    // Full-screen overlay
+    let area = frame.area();
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(" Settings ")
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(accent))
+        .style(Style::default().bg(theme.surface));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(1), Constraint::Length(1)])
+        .split(inner);
+
+    let name_width = SettingKey::ALL
+        .iter()
+        .map(|key| key.name().len())
+        .max()
+        .unwrap_or(0);
+
+    let mut lines = vec![Line::from("")];
+    for (idx, key) in SettingKey::ALL.iter().enumerate() {
+        let is_selected = idx == popup.selected_index;
+        let is_editing = app.input_mode == InputMode::Editing
+            && app.editing_field == Some(EditingField::SettingField(*key));
+
+        let name_style = if is_selected {
+            Style::default().fg(accent).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(app.theme_text_color())
+        };
+
+        let mut spans = vec![
+            Span::styled(
+                if is_selected { " > " } else { "   " },
+                Style::default().fg(accent),
+            ),
+            Span::styled(
+                format!("{:width$}  ", key.name(), width = name_width),
+                name_style,
+            ),
+        ];
+
+        let value = popup.value(*key);
+        if key.is_numeric() {
+            let selection = if is_editing {
+                app.get_selection_range()
+            } else {
+                None
+            };
+            spans.extend(text_with_cursor_and_selection(
+                &value,
+                app.cursor_position,
+                is_editing,
+                "0",
+                Style::default().fg(app.theme_text_color()),
+                selection,
+            ));
+        } else if *key == SettingKey::Theme {
+            spans.push(Span::styled(
+                format!("◀ {} ▶", value),
+                Style::default().fg(app.theme_text_color()),
+            ));
+        } else {
+            spans.push(Span::styled(
+                format!("[{}]", value),
+                Style::default().fg(app.theme_text_color()),
+            ));
+        }
+
+        lines.push(Line::from(spans));
+    }
+
+    frame.render_widget(Paragraph::new(lines), chunks[0]);
+
+    let footer_text = if app.input_mode == InputMode::Editing {
+        " Enter done • Esc done "
+    } else {
+        " ↑/↓ select • Space/←/→ change • e edit number • Enter save • Esc cancel "
+    };
+    let footer = Paragraph::new(Line::from(Span::styled(
+        footer_text,
+        Style::default().fg(app.theme_muted_color()),
+    )))
+    .alignment(Alignment::Center);
+    frame.render_widget(footer, chunks[1]);
+}
@@ -1,11 +1,17 @@
 use crate::app::{App, EditingField, FocusedPanel, InputMode};
+use crate::storage::AuthType;
 use ratatui::{
     layout::Rect,
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph},
+    widgets::{Block, Borders, Clear, Paragraph},
     Frame,
 };
+use regex::Regex;
+
+/// Method badge width: the longest method (DELETE) plus a space either side, so
+/// the URL starts at the same column whatever the method
+const METHOD_BADGE_WIDTH: usize = 8;
 
 pub fn draw(frame: &mut Frame, app: &mut App, area: Rect) {
     let focused = app.focused_panel == FocusedPanel::UrlBar;
@@ -19,6 +25,7 @@ pub fn draw(frame: &mut Frame, app: &mut App, area: Rect) {
         crate::storage::HttpMethod::Put => Color::Blue,
         crate::storage::HttpMethod::Patch => Color::Magenta,
         crate::storage::HttpMethod::Delete => Color::Red,
+        crate::storage::HttpMethod::Head => Color::Cyan,
     };
 
     // URL display with cursor and selection if editing
@@ -89,6 +96,8 @@ pub fn draw(frame: &mut Frame, app: &mut App, area: Rect) {
             "Enter URL... (press Enter or 'i' to edit)",
             Style::default().fg(app.theme_muted_color()),
         )]
+    } else if app.url_preview_mode {
+        interpolated_url_spans(app, url_text)
     } else {
         vec![Span::styled(
             url_text.clone(),
@@ -96,14 +105,22 @@ pub fn draw(frame: &mut Frame, app: &mut App, area: Rect) {
         )]
     };
 
-    // Build the URL line
+    // Build the URL line; the badge flashes briefly after the method is cycled
+    let mut badge_style = Style::default()
+        .fg(Color::Black)
+        .bg(method_color)
+        .add_modifier(Modifier::BOLD);
+    if app.method_flash_active() {
+        badge_style = badge_style.add_modifier(Modifier::REVERSED);
+    }
     let mut spans = vec![
         Span::styled(
-            format!(" {} ", app.current_request.method.as_str()),
-            Style::default()
-                .fg(Color::Black)
-                .bg(method_color)
-                .add_modifier(Modifier::BOLD),
+            format!(
+                "{:^width$}",
+                app.current_request.method.as_str(),
+                width = METHOD_BADGE_WIDTH
+            ),
+            badge_style,
         ),
         Span::raw(" "),
     ];
@@ -126,25 +143,188 @@ pub fn draw(frame: &mut Frame, app: &mut App, area: Rect) {
         Style::default().fg(app.theme_muted_color())
     };
 
+    // Slot indicators, e.g. [1][•2][3], once more than one request is open
+    let mut title = vec![Span::styled(" URL₂ ", title_style)];
+    for index in 0..app.request_slots.len() {
+        let (label, style) = if index == app.active_slot {
+            (format!("[•{}]", index + 1), Style::default().fg(accent))
+        } else {
+            (
+                format!("[{}]", index + 1),
+                Style::default().fg(app.theme_muted_color()),
+            )
+        };
+        title.push(Span::styled(label, style));
+    }
+    if !app.request_slots.is_empty() {
+        title.push(Span::raw(" "));
+    }
+    // The API key is appended to the query string at send time
+    let auth = app.effective_auth();
+    if auth.auth_type == AuthType::ApiKey && auth.api_key_location == "query" {
+        title.push(Span::styled("[+key] ", Style::default().fg(Color::Yellow)));
+    }
+    if let Some(proxy) = app
+        .current_request
+        .proxy_override
+        .as_deref()
+        .filter(|proxy| !proxy.is_empty())
+    {
+        title.push(Span::styled(
+            format!("[proxy: {}] ", proxy),
+            Style::default().fg(Color::Yellow),
+        ));
+    }
+
+    // Clickable raw/preview toggle on the right edge of the border
+    let toggle_label = if app.url_preview_mode {
+        "[preview]"
+    } else {
+        "[raw]"
+    };
+    let toggle_width = toggle_label.len() as u16;
+    app.layout_areas.url_preview_toggle = Some((
+        (area.x + area.width).saturating_sub(toggle_width + 2),
+        area.y,
+        toggle_width,
+    ));
+
+    let mut right_title = Vec::new();
+    if app.show_request_timer {
+        let (text, color) = match app.request_timer_text() {
+            Some(elapsed) if app.last_request_failed => {
+                (format!("⏱ {} since last request", elapsed), Color::Red)
+            }
+            Some(elapsed) => (format!("⏱ {} since last request", elapsed), accent),
+            None => ("⏱ no request sent yet".to_string(), app.theme_muted_color()),
+        };
+        right_title.push(Span::styled(text, Style::default().fg(color)));
+        right_title.push(Span::raw(" "));
+    }
+
     let block = Block::default()
         .borders(Borders::ALL)
         .border_style(border_style)
         .style(Style::default().bg(app.theme_surface_color()))
-        .title(" URL₂ ")
-        .title_style(title_style);
+        .title(Line::from(title))
+        .title_top(Line::from(right_title).right_aligned())
+        .title_top(
+            Line::from(vec![
+                Span::styled(
+                    toggle_label,
+                    if app.url_preview_mode {
+                        Style::default().fg(accent)
+                    } else {
+                        Style::default().fg(app.theme_muted_color())
+                    },
+                ),
+                Span::raw(" "),
+            ])
+            .right_aligned(),
+        );
 
     let url_bar = Paragraph::new(url_line).block(block);
 
     // Calculate where URL text starts for click-to-cursor positioning
-    // Format: [border] [space] [METHOD] [space] [URL text...]
-    // border = 1, method badge = method.len() + 2, space = 1
-    let method_width = app.current_request.method.as_str().len() as u16 + 2; // " GET "
-    let url_text_start = area.x + 1 + method_width + 1; // border + method + space
+    // Format: [border] [METHOD badge] [space] [URL text...]
+    let url_text_start = area.x + 1 + METHOD_BADGE_WIDTH as u16 + 1; // border + badge + space
     app.layout_areas.url_text_start = Some(url_text_start);
 
     frame.render_widget(url_bar, area);
 }
 
+/// URL with `{{VAR}}` placeholders replaced, substituted values in the accent color
+fn interpolated_url_spans(app: &App, url: &str) -> Vec<Span<'static>> {
+    let text_style = Style::default().fg(app.theme_text_color());
+    let value_style = Style::default().fg(app.accent_color());
+    let re = Regex::new(r"\{\{\w+\}\}").unwrap();
+
+    let mut spans = Vec::new();
+    let mut last = 0;
+    for placeholder in re.find_iter(url) {
+        if placeholder.start() > last {
+            spans.push(Span::styled(
+                url[last..placeholder.start()].to_string(),
+                text_style,
+            ));
+        }
+        let value = app.interpolate(placeholder.as_str());
+        // Unknown variables are left as placeholders in the normal color
+        let style = if value == placeholder.as_str() {
+            text_style
+        } else {
+            value_style
+        };
+        spans.push(Span::styled(value, style));
+        last = placeholder.end();
+    }
+    if last < url.len() {
+        spans.push(Span::styled(url[last..].to_string(), text_style));
+    }
+    spans
+}
+
+/// Floating list of URL history matches shown below the URL bar (max 5 lines)
+pub fn draw_url_history(frame: &mut Frame, app: &App) {
+    let Some(cycle) = &app.url_history_cycle else {
+        return;
+    };
+    let Some((x, y, width, height)) = app.layout_areas.url_bar else {
+        return;
+    };
+
+    const MAX_VISIBLE: usize = 5;
+    // Keep the selected entry inside the visible window
+    let start = (cycle.selected + 1).saturating_sub(MAX_VISIBLE);
+    let visible = cycle
+        .matches
+        .iter()
+        .enumerate()
+        .skip(start)
+        .take(MAX_VISIBLE);
+
+    let inner_width = width.saturating_sub(2) as usize;
+    let lines: Vec<Line> = visible
+        .map(|(idx, url)| {
+            let style = if idx == cycle.selected {
+                Style::default()
+                    .fg(app.theme_selection_fg())
+                    .bg(app.theme_selection_bg())
+            } else {
+                Style::default().fg(app.theme_text_color())
+            };
+            let display: String = url.chars().take(inner_width.saturating_sub(2)).collect();
+            Line::from(Span::styled(
+                format!(
+                    " {:width$} ",
+                    display,
+                    width = inner_width.saturating_sub(2)
+                ),
+                style,
+            ))
+        })
+        .collect();
+
+    let area = Rect {
+        x,
+        y: y + height,
+        width,
+        height: (lines.len() as u16 + 2).min(frame.area().height.saturating_sub(y + height)),
+    };
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(format!(
+            " Recent URLs {}/{} ",
+            cycle.selected + 1,
+            cycle.matches.len()
+        ))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.accent_color()))
+        .style(Style::default().bg(app.theme_surface_color()));
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
+
 fn render_url_with_cursor<'a>(
     url_text: &str,
     cursor_pos: usize,
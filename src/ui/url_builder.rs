@@ -0,0 +1,116 @@
+use super::widgets::text_with_cursor_and_selection;
+use crate::app::{App, EditingField, InputMode};
+use crate::storage::{build_url, is_path_variable, UrlSegmentField};
+use ratatui::{
+    layout::{Alignment, Constraint, Flex, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+pub fn draw_url_builder(frame: &mut Frame, app: &App) {
+    let accent = app.accent_color();
+    let theme = app.theme();
+    let segments = &app.url_builder;
+    let fields = segments.fields();
+
+    let popup_width = frame.area().width.saturating_sub(4).clamp(40, 90);
+    let popup_height = (fields.len() + 7).clamp(10, frame.area().height.max(10) as usize) as u16;
+    let area = centered_rect(popup_width, popup_height, frame.area());
+    frame.render_widget(Clear, area);
+
+    let muted = Style::default().fg(app.theme_muted_color());
+    let mut lines = vec![
+        Line::from(Span::styled(
+            build_url(segments),
+            Style::default().fg(app.theme_text_color()),
+        )),
+        Line::from(""),
+    ];
+
+    for (idx, field) in fields.iter().enumerate() {
+        let is_selected = idx == app.url_builder_selected;
+        let is_editing = app.input_mode == InputMode::Editing
+            && app.editing_field == Some(EditingField::UrlSegment(*field));
+        let text = segments.get(*field).map(String::as_str).unwrap_or_default();
+
+        let label = match field {
+            UrlSegmentField::Scheme => "Scheme",
+            UrlSegmentField::Host => "Host",
+            UrlSegmentField::Path(_) if is_path_variable(text) => "Path var",
+            UrlSegmentField::Path(_) => "Path /",
+            UrlSegmentField::QueryKey(_) => "Param",
+            UrlSegmentField::QueryValue(_) => "  value",
+        };
+        let label_style = if is_selected {
+            Style::default().fg(accent).add_modifier(Modifier::BOLD)
+        } else {
+            muted
+        };
+        let text_style = match field {
+            UrlSegmentField::Path(_) if is_path_variable(text) => Style::default()
+                .fg(app.theme_accent_color())
+                .add_modifier(Modifier::BOLD),
+            _ => Style::default().fg(app.theme_text_color()),
+        };
+
+        let mut spans = vec![
+            Span::styled(
+                if is_selected { "> " } else { "  " },
+                Style::default().fg(accent),
+            ),
+            Span::styled(format!("{:<9}", label), label_style),
+        ];
+        let selection = if is_editing {
+            app.get_selection_range()
+        } else {
+            None
+        };
+        spans.extend(text_with_cursor_and_selection(
+            text,
+            app.cursor_position,
+            is_editing,
+            "(empty)",
+            text_style,
+            selection,
+        ));
+        lines.push(Line::from(spans));
+    }
+
+    let block = Block::default()
+        .title(" URL Builder ")
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(accent))
+        .style(Style::default().bg(theme.surface));
+
+    let content = Paragraph::new(lines)
+        .block(block)
+        .wrap(Wrap { trim: false });
+    frame.render_widget(content, area);
+
+    let footer_area = Rect {
+        x: area.x,
+        y: area.y + area.height - 1,
+        width: area.width,
+        height: 1,
+    };
+    let footer_text = if app.input_mode == InputMode::Editing {
+        " Tab next segment • Enter/Esc done "
+    } else {
+        " Tab/j/k move • Enter edit • a add segment • A add param • d delete • Esc close "
+    };
+    let footer = Paragraph::new(Line::from(vec![Span::styled(footer_text, muted)]))
+        .alignment(Alignment::Center);
+    frame.render_widget(footer, footer_area);
+}
+
+fn centered_rect(width: u16, height: u16, area: Rect) -> Rect {
+    let horizontal = Layout::horizontal([Constraint::Length(width)]).flex(Flex::Center);
+    let vertical = Layout::vertical([Constraint::Length(height)]).flex(Flex::Center);
+
+    let [area] = vertical.areas(area);
+    let [area] = horizontal.areas(area);
+    area
+}
@@ -0,0 +1,73 @@
+use crate::app::App;
+use ratatui::{
+    layout::{Alignment, Constraint, Flex, Layout, Rect},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+pub fn draw_verbose_preview(frame: &mut Frame, app: &App) {
+    let accent = app.accent_color();
+
+    let max_line_len = app
+        .verbose_preview_lines
+        .iter()
+        .map(|line| line.chars().count())
+        .max()
+        .unwrap_or(20);
+    let max_width = frame.area().width.saturating_sub(4).max(40) as usize;
+    let popup_width = (max_line_len + 4).clamp(40, max_width) as u16;
+    let popup_height = (app.verbose_preview_lines.len() + 4).clamp(7, 30) as u16;
+    let area = centered_rect(popup_width, popup_height, frame.area());
+    frame.render_widget(Clear, area);
+
+    let lines: Vec<Line> = app
+        .verbose_preview_lines
+        .iter()
+        .enumerate()
+        .map(|(idx, line)| {
+            // First line is the request line, the rest are headers
+            let style = if idx == 0 {
+                Style::default().fg(accent)
+            } else {
+                Style::default().fg(app.theme_text_color())
+            };
+            Line::from(Span::styled(line.clone(), style))
+        })
+        .collect();
+
+    let block = Block::default()
+        .title(" Request Preview ")
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Magenta))
+        .style(Style::default().bg(app.theme_surface_color()));
+
+    let content = Paragraph::new(lines)
+        .block(block)
+        .wrap(Wrap { trim: false });
+    frame.render_widget(content, area);
+
+    let footer_area = Rect {
+        x: area.x,
+        y: area.y + area.height - 1,
+        width: area.width,
+        height: 1,
+    };
+    let footer = Paragraph::new(Line::from(vec![Span::styled(
+        " Enter send • Esc cancel ",
+        Style::default().fg(app.theme_muted_color()),
+    )]))
+    .alignment(Alignment::Center);
+    frame.render_widget(footer, footer_area);
+}
+
+fn centered_rect(width: u16, height: u16, area: Rect) -> Rect {
+    let horizontal = Layout::horizontal([Constraint::Length(width)]).flex(Flex::Center);
+    let vertical = Layout::vertical([Constraint::Length(height)]).flex(Flex::Center);
+
+    let [area] = vertical.areas(area);
+    let [area] = horizontal.areas(area);
+    area
+}
@@ -3,6 +3,43 @@ use ratatui::{
     text::Span,
 };
 
+/// Human-readable byte size (B, KB or MB)
+pub fn format_size(bytes: usize) -> String {
+    if bytes < 1024 {
+        format!("{} B", bytes)
+    } else if bytes < 1024 * 1024 {
+        format!("{:.1} KB", bytes as f64 / 1024.0)
+    } else {
+        format!("{:.1} MB", bytes as f64 / (1024.0 * 1024.0))
+    }
+}
+
+/// Bar of `width` cells showing `size` against `threshold`, followed by `size / threshold`.
+/// Yellow above 90% of the threshold, red at or above it.
+pub fn draw_size_gauge(size: usize, threshold: usize, width: u8) -> Vec<Span<'static>> {
+    let ratio = size as f64 / threshold.max(1) as f64;
+    let filled = ((ratio * width as f64).round() as usize).min(width as usize);
+    let color = if ratio >= 1.0 {
+        Color::Red
+    } else if ratio > 0.9 {
+        Color::Yellow
+    } else {
+        Color::Green
+    };
+
+    vec![
+        Span::styled("▒".repeat(filled), Style::default().fg(color)),
+        Span::styled(
+            "░".repeat(width as usize - filled),
+            Style::default().fg(Color::DarkGray),
+        ),
+        Span::styled(
+            format!(" {} / {}", format_size(size), format_size(threshold)),
+            Style::default().fg(color),
+        ),
+    ]
+}
+
 pub fn text_with_cursor<'a>(
     text: &str,
     cursor_pos: usize,
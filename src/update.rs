@@ -0,0 +1,120 @@
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Latest published release of restui
+const RELEASES_URL: &str = "https://api.github.com/repos/benwyrosdick/restui/releases/latest";
+
+/// How long a cached check result is trusted before asking GitHub again
+const CACHE_HOURS: i64 = 24;
+
+/// Contents of the `.update_check` cache file
+#[derive(Debug, Serialize, Deserialize)]
+struct CachedCheck {
+    checked_at: DateTime<Utc>,
+    latest_version: String,
+}
+
+/// Version of this build
+pub fn current_version() -> &'static str {
+    env!("CARGO_PKG_VERSION")
+}
+
+/// Fetch the tag of the latest GitHub release, without a leading `v`
+pub async fn fetch_latest_version(timeout: std::time::Duration) -> Result<String> {
+    let client = reqwest::Client::builder()
+        .timeout(timeout)
+        // The GitHub API rejects requests without a User-Agent
+        .user_agent(concat!("restui/", env!("CARGO_PKG_VERSION")))
+        .build()?;
+    let response = client
+        .get(RELEASES_URL)
+        .header("Accept", "application/vnd.github+json")
+        .send()
+        .await?
+        .error_for_status()?;
+    let json: serde_json::Value = response.json().await?;
+    let tag = json
+        .get("tag_name")
+        .and_then(serde_json::Value::as_str)
+        .ok_or_else(|| anyhow!("Release has no tag_name"))?;
+    Ok(tag.trim_start_matches('v').to_string())
+}
+
+/// Latest version from the cache file, if it was checked within the last day
+pub fn cached_latest_version(path: &Path) -> Option<String> {
+    let content = std::fs::read_to_string(path).ok()?;
+    let cached: CachedCheck = serde_json::from_str(&content).ok()?;
+    (Utc::now() - cached.checked_at < Duration::hours(CACHE_HOURS)).then_some(cached.latest_version)
+}
+
+pub fn save_cache(path: &Path, latest_version: &str) -> Result<()> {
+    let cached = CachedCheck {
+        checked_at: Utc::now(),
+        latest_version: latest_version.to_string(),
+    };
+    std::fs::write(path, serde_json::to_string(&cached)?)?;
+    Ok(())
+}
+
+/// Latest version (cached for a day), or None when this build is already current
+pub async fn check(cache_path: &Path, timeout: std::time::Duration) -> Result<Option<String>> {
+    let latest = match cached_latest_version(cache_path) {
+        Some(latest) => latest,
+        None => {
+            let latest = fetch_latest_version(timeout).await?;
+            save_cache(cache_path, &latest)?;
+            latest
+        }
+    };
+    Ok(is_newer(&latest, current_version()).then_some(latest))
+}
+
+/// Compare dotted numeric versions; pre-release suffixes are ignored
+pub fn is_newer(latest: &str, current: &str) -> bool {
+    version_parts(latest) > version_parts(current)
+}
+
+fn version_parts(version: &str) -> Vec<u64> {
+    version
+        .trim_start_matches('v')
+        .split(['-', '+'])
+        .next()
+        .unwrap_or_default()
+        .split('.')
+        .map(|part| part.parse().unwrap_or(0))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_newer() {
+        assert!(is_newer("0.3.0", "0.2.9"));
+        assert!(is_newer("v1.10.0", "1.9.3"));
+        assert!(is_newer("1.0.1", "1.0"));
+        assert!(!is_newer("1.0.0", "1.0.0"));
+        assert!(!is_newer("0.9.0", "1.0.0"));
+        assert!(!is_newer("1.0.0-beta.1", "1.0.0"));
+    }
+
+    #[test]
+    fn test_cache_round_trip() {
+        let path = std::env::temp_dir().join(format!(".update_check-{}", uuid::Uuid::new_v4()));
+        assert_eq!(cached_latest_version(&path), None);
+
+        save_cache(&path, "1.2.3").unwrap();
+        assert_eq!(cached_latest_version(&path).as_deref(), Some("1.2.3"));
+
+        let stale = CachedCheck {
+            checked_at: Utc::now() - Duration::hours(CACHE_HOURS + 1),
+            latest_version: "1.2.3".to_string(),
+        };
+        std::fs::write(&path, serde_json::to_string(&stale).unwrap()).unwrap();
+        assert_eq!(cached_latest_version(&path), None);
+        let _ = std::fs::remove_file(&path);
+    }
+}